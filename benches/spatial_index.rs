@@ -0,0 +1,87 @@
+// Spatial index against a linear scan over the same rectangles.
+// Run with `cargo bench`, the numbers are printed, there's no harness.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::SpatialIndex;
+use rust_progr_lang_course::Rectangle;
+
+const QUERIES: usize = 1000;
+
+fn time<T>(mut f: impl FnMut() -> T) -> (Duration, T)
+{
+    let start = Instant::now();
+    let result = f();
+    (start.elapsed(), result)
+}
+
+fn report(what: &str, scan: Duration, index: Duration)
+{
+    let speedup = scan.as_secs_f64() / index.as_secs_f64().max(f64::MIN_POSITIVE);
+    println!("  {what:8} scan {scan:>12.3?}  index {index:>12.3?}  {speedup:>7.1}x");
+}
+
+fn bench(count: usize)
+{
+    let mut rng = StdRng::seed_from_u64(count as u64);
+    let rects: Vec<Rectangle> = (0..count)
+        .map(|_| Rectangle::at(rng.gen_range(0..10_000), rng.gen_range(0..10_000), rng.gen_range(1..50), rng.gen_range(1..50)))
+        .collect();
+    let regions: Vec<Rectangle> = (0..QUERIES)
+        .map(|_| Rectangle::at(rng.gen_range(0..10_000), rng.gen_range(0..10_000), rng.gen_range(1..200), rng.gen_range(1..200)))
+        .collect();
+    let points: Vec<(i32, i32)> = (0..QUERIES).map(|_| (rng.gen_range(0..10_000), rng.gen_range(0..10_000))).collect();
+
+    println!("{count} rectangles, {QUERIES} queries each:");
+
+    let (build, index) = time(|| {
+        let mut index = SpatialIndex::new();
+        for r in &rects
+        {
+            index.insert(*r);
+        }
+        index
+    });
+    println!("  built the index in {build:.3?}");
+
+    let (scan, scanned) = time(|| regions.iter().map(|q| rects.iter().filter(|r| r.intersects(q)).count()).sum::<usize>());
+    let (indexed, found) = time(|| regions.iter().map(|q| index.query(q).len()).sum::<usize>());
+    assert_eq!(black_box(scanned), black_box(found));
+    report("region", scan, indexed);
+
+    let (scan, scanned) = time(|| points.iter().map(|&(x, y)| rects.iter().filter(|r| r.contains_point(x, y)).count()).sum::<usize>());
+    let (indexed, found) = time(|| points.iter().map(|&(x, y)| index.at_point(x, y).len()).sum::<usize>());
+    assert_eq!(black_box(scanned), black_box(found));
+    report("point", scan, indexed);
+
+    let (scan, scanned) = time(|| {
+        points
+            .iter()
+            .map(|&(x, y)| {
+                let (x, y) = (i64::from(x), i64::from(y));
+                (0..rects.len())
+                    .min_by_key(|&i| {
+                        let r = &rects[i];
+                        let dx = (r.left() - x).max(x - r.right()).max(0);
+                        let dy = (r.top() - y).max(y - r.bottom()).max(0);
+                        dx * dx + dy * dy
+                    })
+                    .unwrap()
+            })
+            .sum::<usize>()
+    });
+    let (indexed, found) = time(|| points.iter().map(|&(x, y)| index.nearest(x, y).unwrap().index()).sum::<usize>());
+    assert_eq!(black_box(scanned), black_box(found));
+    report("nearest", scan, indexed);
+}
+
+fn main()
+{
+    for count in [1_000, 10_000, 100_000]
+    {
+        bench(count);
+    }
+}
@@ -1,59 +1,11 @@
-use std::cell::RefMut;
-use std::cmp::Ordering;
-use std::io;
-use rand::Rng;
-
-mod structs_enums;
-mod common_collections;
-mod error_handling;
-mod generics;
-mod minigrep;
-mod iterators_closures;
-mod smart_pointers;
-
-use smart_pointers::{
-    chapter_15_deref
-};
-
-use structs_enums::{chapter_5_1_structs, 
-    chapter_5_2_exmpl_prog_with_structs,
-    chapter_5_3_struct_methods,
-    chapter_6_2_MATCH};
-
-use common_collections::{
-    chapter_8_vectors,
-    chapter_8_strings
-};
-
-
-use error_handling::{
-    chapter_9_recoverable_errors
-};
-
-use generics::{
-    chapter_10_generics
-};
-
-use minigrep::{
-    grep_main
-};
-
-use crate::smart_pointers::chapter_15_deref;
-
-fn main() {
-    // chapter_5_1_structs();
-    // chapter_5_2_exmpl_prog_with_structs();
-    // chapter_5_3_struct_methods();
-    // chapter_6_2_MATCH();
-    // chapter_8_vectors();
-    // chapter_8_strings();
-    // chapter_9_recoverable_errors();
-    // chapter_10_generics();
-    grep_main();
-}
+// Chapters 3 and 4 of the book: variables, types, functions, control flow,
+// ownership, borrowing and slices
+
+#![allow(unused, non_snake_case)]
 
+use crate::checks::Check;
 
-fn chapter_4_3_SLICES()
+pub fn chapter_4_3_SLICES()
 {
     let is_slices = true;
     let is_string_literals = true;
@@ -69,25 +21,8 @@ fn chapter_4_3_SLICES()
 
         println!("############ WITHOUT SLICES #############");
         // We don’t need the ownership of string, so we use reference.
-        // Our function will return idx of the end of the word
-        fn first_word(s: &String) -> usize 
-        {
-            // we want to go through string elem by elem and check if value is 
-            // a space --> we convert our string to an ARRAY OF BYTES
-            let bytes = s.as_bytes();
-
-            // We create iterator over the bytes array using iter.() 
-            // then we use enumerate() so that we have elements idxs
-            // enumerate returns tuple --> (idx, &ref_to_data) 
-            for (i, &item) in bytes.iter().enumerate()
-            {
-                if item == b' '
-                {
-                    return i;
-                }
-            }
-            return s.len();
-        }
+        // Our function (first_word below this function) will return idx of
+        // the end of the word
 
         // We now have a way to find out the index of the end of the first word in the string, but there’s a problem. We’re returning a usize on its own, but it’s only a meaningful number in the context of the &String. In other words, because it’s a separate value from the String, there’s no guarantee that it will still be valid in the future.
 
@@ -177,20 +112,7 @@ fn chapter_4_3_SLICES()
 
         // full expl: https://users.rust-lang.org/t/understanding-when-to-use-string-vs-str/103746/2
 
-        fn first_word_better(s: &String) -> &str 
-        {
-            let bytes = s.as_bytes();
-
-            for (i, &item) in bytes.iter().enumerate()
-            {
-                if item == b' '
-                {
-                    return &s[..i];
-                }
-            }
-            return &s[..];
-        }
-
+        // (first_word_better is below this function too)
         let s = String::from("test string xd");
         let x = first_word_better(&s); // immutable borrow
 
@@ -230,7 +152,40 @@ fn chapter_4_3_SLICES()
     }
 }
 
-fn chapter_4_2_REFERENCES()
+fn first_word(s: &String) -> usize 
+{
+    // we want to go through string elem by elem and check if value is 
+    // a space --> we convert our string to an ARRAY OF BYTES
+    let bytes = s.as_bytes();
+
+    // We create iterator over the bytes array using iter.() 
+    // then we use enumerate() so that we have elements idxs
+    // enumerate returns tuple --> (idx, &ref_to_data) 
+    for (i, &item) in bytes.iter().enumerate()
+    {
+        if item == b' '
+        {
+            return i;
+        }
+    }
+    s.len()
+}
+
+fn first_word_better(s: &String) -> &str 
+{
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate()
+    {
+        if item == b' '
+        {
+            return &s[..i];
+        }
+    }
+    &s[..]
+}
+
+pub fn chapter_4_2_REFERENCES()
 {
     // A REFERENCE IS LIKE A POINTER 
     // ---> it’s an address we can follow to access the data stored at that address; that data is owned by some other variable.
@@ -264,11 +219,8 @@ fn chapter_4_2_REFERENCES()
         // Unlike a pointer, a reference is GUARANTEED TO POINT TO A VALID VALUE of a particular type for the life of that reference.
 
         // Instead of giving up our ownership, we pass to function a REFERENCE
-        // using '&' symbol. We now don't need to use tuples!!
-        fn calc_len_with_reference(s: &String) -> usize 
-        {
-            s.len()
-        } // // Here, s goes out of scope. But because s does not have ownership of what it refers to, the String is not dropped.
+        // using '&' symbol. We now don't need to use tuples!! (see
+        // calc_len_with_reference below this function)
 
         let s = String::from("string for reference");
         let len = calc_len_with_reference(&s);
@@ -290,11 +242,7 @@ fn chapter_4_2_REFERENCES()
         //     some_string.push_str(", world");
         // }
 
-        fn change_mut_red(ref_mutable_str: &mut String)
-        {
-            ref_mutable_str.push_str("string added in function");
-        }
-
+        // change_mut_red below this function takes a &mut String instead
         // function takes a reference to mutable string, so for it to work we 
         // also need to create a mutable string
         let mut str_mut: String = String::from("mutable string ");
@@ -385,7 +333,19 @@ fn chapter_4_2_REFERENCES()
     
 }
 
-fn chapter_4_1_OWNERSHIP()
+// takes &String rather than &str, the lesson is about borrowing a String
+#[allow(clippy::ptr_arg)]
+fn calc_len_with_reference(s: &String) -> usize 
+{
+    s.len()
+} // // Here, s goes out of scope. But because s does not have ownership of what it refers to, the String is not dropped.
+
+fn change_mut_red(ref_mutable_str: &mut String)
+{
+    ref_mutable_str.push_str("string added in function");
+}
+
+pub fn chapter_4_1_OWNERSHIP()
 {
     let is_ownership = true;
     let is_string_type = true;
@@ -508,6 +468,7 @@ fn chapter_4_1_OWNERSHIP()
             println!("My arg is integer, so Deep copy = Shallow copy, so integers have COPY TRAIT, thus I got a copy in my argument, x={x}");
         }
 
+        #[allow(clippy::let_and_return)]
         fn give_ownership() -> String
         {
             // we create new string, and then return it, thus this new_s loses
@@ -549,7 +510,7 @@ fn chapter_4_1_OWNERSHIP()
 
 }
 
-fn chapter_3_common_programming_concepts()
+pub fn chapter_3_common_programming_concepts()
 {
     let is_variables = true;
     let is_data_types = true;
@@ -577,14 +538,9 @@ fn chapter_3_common_programming_concepts()
         // --------- SHADOWING ---------
         // - you can declare a new variable with the same name as a previous 
         //   variable
-        let a = 65;
-        let a = a + 4;
-        println!("shadowed 'a' value {a}");
-        {
-            let a = a + 6900;
-            println!("shadowed 'a' inside another scope {a}"); // will print 6969
-        }
-
+        // (see shadowing below this function)
+        let (a, inner) = shadowing();
+        println!("shadowed 'a' inside another scope {inner}"); // will print 6969
         println!("shadowed 'a' after the scope {a}"); // will print 69
 
         // Difference between mut and shadowing is that because we’re effectively 
@@ -602,32 +558,18 @@ fn chapter_3_common_programming_concepts()
 
         // To explicitly handle integer overflows you need to use:
         // i.e. WRAPPING_ADD, CHECKED_ADD
-        let mut sum: i8 = 0;
-        for i in 1..127 {
-            // we will just do modulo, and continue with calculating sum
-            sum = sum.wrapping_add(i); 
+        // (see wrapping_sums and checked_sums below this function)
+        for sum in wrapping_sums()
+        {
             print!("{sum},");
-            if i > 17
-            {
-                break;
-            }
         }
         println!("\n##########################\n##################");
-        sum = 0;
 
-        for i in 1..127
+        for sum in checked_sums()
         {
             print!("{sum} ");
-            sum = match sum.checked_add(i)
-            {
-                Some(val) => val,
-                None => {
-                    println!("GOT OVERFLOW");
-                    break;
-                }
-            };
-
         }
+        println!("GOT OVERFLOW");
 
         // ------- TUPLES ------
         println!("###### TUPLES ######");
@@ -683,13 +625,8 @@ fn chapter_3_common_programming_concepts()
             x + 1
         };
 
-        // function declaration:
-        fn plus_one(x: i32) -> i32 {
-            x + 1 // this is an expression, thus it evaluates, thus we dont need ;
-        }
-
-        // But if we did x+1;, we would get an error, since x+1; is no longer 
-        // an expression, but a statement
+        // function declaration: see plus_one below this function
+        println!("plus_one({y}) = {}", plus_one(y));
     }
 
     // --------- CONDITIONS ----------
@@ -726,14 +663,8 @@ fn chapter_3_common_programming_concepts()
         // -> will loop forever
         // One of the uses of a loop is to retry an operation you know might fail, such as checking whether a thread has completed its job. You might also need to PASS THE RESULT OF THAT OPERATION OUT OF THE LOOP to the rest of your code. To do this, you can ADD THE VALUE YOU WANT RETURNED AFTER THE BREAK expression you use to stop the loop; that value will be returned out of the loop
 
-        let mut counter = 0;
-        let result = loop {
-            counter += 1;
-            if counter > 10 
-            {
-                break counter * counter
-            }
-        };
+        // (see break_with_value below this function)
+        let result = break_with_value();
         println!("result of breaking from loop {result}");
 
         // !!!!!!!!!!!  LOOP LABELS  !!!!!!!!!!!!!!
@@ -771,7 +702,7 @@ fn chapter_3_common_programming_concepts()
         // for loop with ranges
         println!("-- FOR LOOP WITH RANGES --");
 
-        for nbr in (1..=5).rev()
+        for nbr in countdown()
         {
             print!("{nbr},");
         }
@@ -780,66 +711,114 @@ fn chapter_3_common_programming_concepts()
 
 }
 
-fn chapter_2_guessing_game()
+// 'a' after and inside another scope
+#[allow(clippy::let_and_return)]
+fn shadowing() -> (i32, i32)
 {
-    println!("Guess the number!");
-
-    // 1..=100 is INCLUSIVE RANGE, meaning we include both 1 and 100
-    // and create an array of numbers from 1 to 100
-    // 1..100 is EXCLUSIVE RANGE, 100 is not included
-    // gen_range method takes range as argument (start..=end)
-    let secret_number = rand::thread_rng().gen_range(1..=50);
+    let a = 65;
+    let a = a + 4;
+    let inner = {
+        let a = a + 6900;
+        a // 6969
+    };
+
+    (a, inner) // a is 69 again
+}
 
-    // loop creates infty loop
-    loop {
-        println!("Please input your guess.");
-
-        let mut guess = String::new();
-
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        // We need to convert guess: String to u32 - secret_nbr type
-        // We SHADOW previous guess with a new one.
-        // - trim() --> eliminate any whitespace at the beginning and end
-        // - parse() --> parse method on strings converts a string to another 
-        //               type; we need to specify this type after :
-        //               Because parse might fail, the parse method returns a 
-        //               Result type. If it returns **Err** expect will catch 
-        //               it, otherwise it will return **Ok** 
-        // - expect() --> instance of RESULT has expect method so if Result is 
-        //                Err expect will cause programme to crash
-        // 
-        // old version: our programme crashed when encountered not a number
-        // let guess: u32 = guess.trim().parse().expect("Please type a number");
+// The sums of 1 to 18 in an i8, which overflows after 15
+fn wrapping_sums() -> Vec<i8>
+{
+    let mut sums = Vec::new();
+    let mut sum: i8 = 0;
+    for i in 1..127 {
+        // we will just do modulo, and continue with calculating sum
+        sum = sum.wrapping_add(i); 
+        sums.push(sum);
+        if i > 17
+        {
+            break;
+        }
+    }
+    sums
+}
 
-        // new version: we use pattern matching to handle Err case
-        let guess: u32 = match guess.trim().parse() 
+// The sums of 1, 2, ... in an i8 up to the last one before it overflows,
+// starting with 0
+fn checked_sums() -> Vec<i8>
+{
+    let mut sums = Vec::new();
+    let mut sum: i8 = 0;
+    for i in 1..127
+    {
+        sums.push(sum);
+        sum = match sum.checked_add(i)
         {
-            Ok(num) => {
-                num
-            },
-            Err(_) => {
-                println!("Type a number!");
-                continue;
-            }
+            Some(val) => val,
+            None => break,
         };
+    }
+    sums
+}
+
+fn plus_one(x: i32) -> i32 {
+    x + 1 // this is an expression, thus it evaluates, thus we dont need ;
+}
 
-        // Like in haskell, we have pattern matching , 
-        // cmp returns Less, Greater of Equal value
-        // More formally: A match expression is made up of arms. An arm consists of 
-        // a pattern to match against, and the code that should be run if the value 
-        // given to match fits that arm’s pattern
-        match guess.cmp(&secret_number)
+// But if we did x+1;, we would get an error, since x+1; is no longer 
+// an expression, but a statement
+
+fn break_with_value() -> i32
+{
+    let mut counter = 0;
+    loop {
+        counter += 1;
+        if counter > 10 
         {
-            Ordering::Less => println!("Too small"),
-            Ordering::Greater => println!("Too big"),
-            Ordering::Equal => {
-                println!("You've guessed right!");
-                break;
-            }
+            break counter * counter
         }
-        println!("---------------------");
     }
 }
+
+// for loop with ranges, backwards
+fn countdown() -> Vec<i32>
+{
+    (1..=5).rev().collect()
+}
+
+// ############################## CHECKS ##################################
+
+pub fn chapter_3_common_programming_concepts_checks() -> Vec<Check>
+{
+    vec![
+        Check::new("shadowing keeps the outer value", (69, 6969), shadowing()),
+        Check::new("wrapping_add overflows to negative", Some(&-85), wrapping_sums().last()),
+        Check::new("checked_add stops before overflow", Some(&120), checked_sums().last()),
+        Check::new("a function body ends in an expression", 4, plus_one(3)),
+        Check::new("break returns a value from loop", 121, break_with_value()),
+        Check::new("reversed range", vec![5, 4, 3, 2, 1], countdown()),
+    ]
+}
+
+pub fn chapter_4_2_references_checks() -> Vec<Check>
+{
+    let s = String::from("string for reference");
+    let mut str_mut = String::from("mutable string ");
+    change_mut_red(&mut str_mut);
+
+    vec![
+        Check::new("borrowed length", 20, calc_len_with_reference(&s)),
+        Check::new("changed through &mut", "mutable string string added in function", str_mut.as_str()),
+    ]
+}
+
+pub fn chapter_4_3_slices_checks() -> Vec<Check>
+{
+    let s = String::from("hello world");
+
+    vec![
+        Check::new("end of the first word", 5, first_word(&s)),
+        Check::new("single word is the whole string", 5, first_word(&String::from("hello"))),
+        Check::new("first word as a slice", "hello", first_word_better(&s)),
+        Check::new("single word slice", "hello", first_word_better(&String::from("hello"))),
+    ]
+}
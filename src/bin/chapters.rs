@@ -0,0 +1,72 @@
+use std::env;
+use std::process::ExitCode;
+
+use rust_progr_lang_course::chapters::{self, Chapter};
+use rust_progr_lang_course::guessing_game::game_main;
+use rust_progr_lang_course::minigrep::grep_main;
+
+const USAGE: &str = "\
+Usage: cargo run -- <COMMAND>
+
+Commands:
+  list                  list all chapters
+  run <CHAPTER>...      run chapters by name, \"8\" runs every chapter 8 lesson
+  run --all             run every non-interactive chapter
+  check [CHAPTER]...    verify what the chapters teach, all of them by default
+  game [OPTIONS]        play the guessing game, same as the guessing_game binary
+  grep <ARGS>...        run minigrep, same as the minigrep binary";
+
+fn select(selectors: &[String]) -> Option<Vec<&'static Chapter>>
+{
+    let mut selected = Vec::new();
+    for selector in selectors
+    {
+        let found = chapters::find(selector);
+        if found.is_empty()
+        {
+            eprintln!("unknown chapter '{selector}', see `cargo run -- list`");
+            return None;
+        }
+        selected.extend(found);
+    }
+    Some(selected)
+}
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str)
+    {
+        Some("list") => {
+            chapters::list();
+            ExitCode::SUCCESS
+        }
+        Some("run") if args[1..] == ["--all"] => chapters::run_all(),
+        Some("run") if args[1..].iter().any(|a| a == "--all") => {
+            eprintln!("run --all runs every chapter, it can't be combined with chapter names");
+            ExitCode::from(2)
+        }
+        Some("run") if args.len() > 1 => match select(&args[1..])
+        {
+            Some(selected) => chapters::run(&selected),
+            None => ExitCode::from(2),
+        },
+        Some("check") if args.len() == 1 => chapters::check(&chapters::CHAPTERS.iter().collect::<Vec<_>>()),
+        Some("check") => match select(&args[1..])
+        {
+            Some(selected) => chapters::check(&selected),
+            None => ExitCode::from(2),
+        },
+        Some("game") => game_main(&args[1..]),
+        Some("grep") => grep_main(&args[1..]),
+        Some("-h" | "--help" | "help") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
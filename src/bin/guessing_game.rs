@@ -0,0 +1,10 @@
+use std::env;
+use std::process::ExitCode;
+
+use rust_progr_lang_course::guessing_game::game_main;
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    game_main(&args)
+}
@@ -0,0 +1,10 @@
+use std::env;
+use std::process::ExitCode;
+
+use rust_progr_lang_course::minigrep::grep_main;
+
+fn main() -> ExitCode
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    grep_main(&args)
+}
@@ -0,0 +1,254 @@
+use std::panic;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::checks::{no_checks, Check};
+use crate::common_collections::{chapter_8_strings, chapter_8_strings_checks, chapter_8_vectors, chapter_8_vectors_checks};
+use crate::error_handling::{chapter_9_recoverable_errors, chapter_9_unrecoverable_errors};
+use crate::generics::{chapter_10_generics, chapter_10_generics_checks, chapter_10_lifetimes, chapter_10_lifetimes_checks};
+use crate::iterators_closures::{
+    chapter_13_closures,
+    chapter_13_closures_checks,
+    chapter_13_iterators,
+    chapter_13_iterators_checks,
+};
+use crate::smart_pointers::{chapter_15_Box_pointer, chapter_15_box_checks, chapter_15_deref, chapter_15_deref_checks};
+use crate::structs_enums::{
+    chapter_5_1_structs,
+    chapter_5_2_exmpl_prog_with_structs,
+    chapter_5_3_struct_methods,
+    chapter_5_3_struct_methods_checks,
+    chapter_6_1_enums,
+    chapter_6_2_MATCH,
+    chapter_6_2_match_checks,
+    chapter_6_2_OPTION_enum,
+    chapter_6_3_if_let,
+};
+use crate::guessing_game::chapter_2_guessing_game;
+use crate::basics::{
+    chapter_3_common_programming_concepts,
+    chapter_3_common_programming_concepts_checks,
+    chapter_4_1_OWNERSHIP,
+    chapter_4_2_REFERENCES,
+    chapter_4_2_references_checks,
+    chapter_4_3_SLICES,
+    chapter_4_3_slices_checks,
+};
+
+pub struct Chapter
+{
+    // "<book chapter>.<topic>", e.g. "8.strings"
+    pub name: &'static str,
+    pub title: &'static str,
+    pub run: fn(),
+    // chapters waiting for stdin are left out of `run --all`
+    pub interactive: bool,
+    // chapters demonstrating panic! aren't counted as failures
+    pub expect_panic: bool,
+    // what the chapter teaches, verified by `check` and `cargo test`
+    pub checks: fn() -> Vec<Check>,
+}
+
+const fn chapter(name: &'static str, title: &'static str, run: fn()) -> Chapter
+{
+    Chapter { name, title, run, interactive: false, expect_panic: false, checks: no_checks }
+}
+
+// In the order of the book
+pub const CHAPTERS: &[Chapter] = &[
+    Chapter {
+        interactive: true,
+        ..chapter("2.guessing_game", "Programming a Guessing Game", chapter_2_guessing_game)
+    },
+    Chapter { checks: chapter_3_common_programming_concepts_checks, ..chapter("3.common_concepts", "Common Programming Concepts", chapter_3_common_programming_concepts) },
+    chapter("4.1.ownership", "What Is Ownership?", chapter_4_1_OWNERSHIP),
+    Chapter { checks: chapter_4_2_references_checks, ..chapter("4.2.references", "References and Borrowing", chapter_4_2_REFERENCES) },
+    Chapter { checks: chapter_4_3_slices_checks, ..chapter("4.3.slices", "The Slice Type", chapter_4_3_SLICES) },
+    chapter("5.1.structs", "Defining and Instantiating Structs", chapter_5_1_structs),
+    chapter("5.2.structs_example", "An Example Program Using Structs", chapter_5_2_exmpl_prog_with_structs),
+    Chapter { checks: chapter_5_3_struct_methods_checks, ..chapter("5.3.methods", "Method Syntax", chapter_5_3_struct_methods) },
+    chapter("6.1.enums", "Defining an Enum", chapter_6_1_enums),
+    Chapter { checks: chapter_6_2_match_checks, ..chapter("6.2.match", "The match Control Flow Construct", chapter_6_2_MATCH) },
+    chapter("6.2.option", "The Option Enum", chapter_6_2_OPTION_enum),
+    chapter("6.3.if_let", "Concise Control Flow with if let and let else", chapter_6_3_if_let),
+    Chapter { checks: chapter_8_vectors_checks, ..chapter("8.vectors", "Storing Lists of Values with Vectors", chapter_8_vectors) },
+    Chapter { checks: chapter_8_strings_checks, ..chapter("8.strings", "Storing UTF-8 Encoded Text with Strings", chapter_8_strings) },
+    chapter("9.recoverable_errors", "Recoverable Errors with Result", chapter_9_recoverable_errors),
+    Chapter {
+        expect_panic: true,
+        ..chapter("9.unrecoverable_errors", "Unrecoverable Errors with panic!", chapter_9_unrecoverable_errors)
+    },
+    Chapter { checks: chapter_10_generics_checks, ..chapter("10.generics", "Generic Data Types and Traits", chapter_10_generics) },
+    Chapter { checks: chapter_10_lifetimes_checks, ..chapter("10.lifetimes", "Validating References with Lifetimes", chapter_10_lifetimes) },
+    Chapter { checks: chapter_13_closures_checks, ..chapter("13.closures", "Closures", chapter_13_closures) },
+    Chapter { checks: chapter_13_iterators_checks, ..chapter("13.iterators", "Processing a Series of Items with Iterators", chapter_13_iterators) },
+    Chapter { checks: chapter_15_box_checks, ..chapter("15.box", "Using Box<T> to Point to Data on the Heap", chapter_15_Box_pointer) },
+    Chapter { checks: chapter_15_deref_checks, ..chapter("15.deref", "Treating Smart Pointers Like Regular References", chapter_15_deref) },
+];
+
+// "8" selects every chapter 8 lesson, "8.strings" only one of them
+pub fn find(selector: &str) -> Vec<&'static Chapter>
+{
+    CHAPTERS
+        .iter()
+        .filter(|c| {
+            c.name == selector
+                || c.name.strip_prefix(selector).is_some_and(|rest| rest.starts_with('.'))
+        })
+        .collect()
+}
+
+pub fn list()
+{
+    let width = CHAPTERS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+
+    for c in CHAPTERS
+    {
+        let note = match (c.interactive, c.expect_panic)
+        {
+            (true, _) => "  (interactive)",
+            (_, true) => "  (panics)",
+            _ => "",
+        };
+        println!("{:width$}  {}{note}", c.name, c.title);
+    }
+}
+
+// Runs chapters one after another and prints how long each of them took.
+// A panicking chapter doesn't stop the others, it is reported at the end.
+pub fn run(chapters: &[&Chapter]) -> ExitCode
+{
+    // None means the chapter panicked when it wasn't supposed to
+    let mut timings: Vec<(&str, Option<Duration>)> = Vec::new();
+
+    for c in chapters
+    {
+        println!("==================== {} - {} ====================", c.name, c.title);
+
+        let start = Instant::now();
+        let result = panic::catch_unwind(c.run);
+        let elapsed = start.elapsed();
+
+        match result
+        {
+            Ok(()) => {
+                println!("-------------------- {} finished in {elapsed:?}\n", c.name);
+                timings.push((c.name, Some(elapsed)));
+            }
+            Err(_) if c.expect_panic => {
+                println!("-------------------- {} panicked as expected after {elapsed:?}\n", c.name);
+                timings.push((c.name, Some(elapsed)));
+            }
+            Err(_) => {
+                println!("-------------------- {} PANICKED after {elapsed:?}\n", c.name);
+                timings.push((c.name, None));
+            }
+        }
+    }
+
+    if timings.len() > 1
+    {
+        println!("Summary:");
+        for (name, elapsed) in &timings
+        {
+            match elapsed
+            {
+                Some(elapsed) => println!("  {name:24} {elapsed:?}"),
+                None => println!("  {name:24} panicked"),
+            }
+        }
+    }
+
+    if timings.iter().all(|(_, elapsed)| elapsed.is_some())
+    {
+        ExitCode::SUCCESS
+    }
+    else
+    {
+        ExitCode::FAILURE
+    }
+}
+
+pub fn run_all() -> ExitCode
+{
+    let chapters: Vec<&Chapter> = CHAPTERS.iter().filter(|c| !c.interactive).collect();
+    run(&chapters)
+}
+
+// How a chapter's checks went, a panicking check function counts as failed
+struct CheckReport
+{
+    checks: Result<Vec<Check>, String>,
+}
+
+impl CheckReport
+{
+    fn passed(&self) -> usize
+    {
+        self.checks.as_ref().map_or(0, |checks| checks.iter().filter(|c| c.passed).count())
+    }
+
+    fn ok(&self) -> bool
+    {
+        self.checks.as_ref().is_ok_and(|checks| checks.iter().all(|c| c.passed))
+    }
+}
+
+// Runs the checks of every given chapter, printing one line per chapter and
+// what went wrong for the failing ones, followed by how far the course is
+pub fn check(chapters: &[&Chapter]) -> ExitCode
+{
+    let width = chapters.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut reports = Vec::new();
+
+    for chapter in chapters
+    {
+        let checks = panic::catch_unwind(chapter.checks).map_err(|payload| {
+            payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic"))
+        });
+        let report = CheckReport { checks };
+
+        match &report.checks
+        {
+            Ok(checks) if checks.is_empty() => println!("{:width$}  no checks yet", chapter.name),
+            Ok(checks) => {
+                let mark = if report.ok() { "ok" } else { "FAILED" };
+                println!("{:width$}  {}/{} passed  {mark}", chapter.name, report.passed(), checks.len());
+                for c in checks.iter().filter(|c| !c.passed)
+                {
+                    println!("    {}: expected {}, got {}", c.name, c.expected, c.actual);
+                }
+            }
+            Err(message) => println!("{:width$}  PANICKED: {message}", chapter.name),
+        }
+        reports.push(report);
+    }
+
+    let with_checks: Vec<&CheckReport> = reports
+        .iter()
+        .filter(|r| r.checks.as_ref().map_or(true, |checks| !checks.is_empty()))
+        .collect();
+    let total: usize = with_checks.iter().map(|r| r.checks.as_ref().map_or(0, Vec::len)).sum();
+    let passed: usize = with_checks.iter().map(|r| r.passed()).sum();
+    let done = with_checks.iter().filter(|r| r.ok()).count();
+
+    println!();
+    println!("{passed}/{total} checks passed, {done}/{} chapters done", reports.len());
+    if with_checks.len() < reports.len()
+    {
+        println!("{} chapters have no checks yet", reports.len() - with_checks.len());
+    }
+
+    if with_checks.iter().all(|r| r.ok())
+    {
+        ExitCode::SUCCESS
+    }
+    else
+    {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,35 @@
+// Exercise checks: what a chapter teaches, written down as facts that can
+// be verified. Every check has a name, the value the book says we get and
+// the value the code actually gives. They run with `cargo test` and with
+// `cargo run -- check`.
+
+use std::fmt::Debug;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check
+{
+    pub name: &'static str,
+    // both sides in their {:?} form, so checks of any type fit in one list
+    pub expected: String,
+    pub actual: String,
+    pub passed: bool,
+}
+
+impl Check
+{
+    pub fn new<T: Debug + PartialEq>(name: &'static str, expected: T, actual: T) -> Check
+    {
+        Check {
+            name,
+            expected: format!("{expected:?}"),
+            actual: format!("{actual:?}"),
+            passed: expected == actual,
+        }
+    }
+}
+
+// For chapters that don't have checks (yet)
+pub fn no_checks() -> Vec<Check>
+{
+    Vec::new()
+}
@@ -1,3 +1,7 @@
+#![allow(unused, non_snake_case)]
+
+use crate::checks::Check;
+
 
 pub fn chapter_8_strings()
 {
@@ -22,26 +26,14 @@ pub fn chapter_8_strings()
     let s1 = String::from("Hello, ");
     let s2 = String::from("world!");
 
-    // we need a reference to &s2 since '+' in real is an add function that 
-    // has signature: 
-    //
-    // fn add(self, s: &str) -> String { 
-    //
-    // so s1 ownership is taken by s3, while s2 will still exist 
-    let s3 = s1 + &s2;
+    // see concatenate below this function, s1 is moved into it
+    let s3 = concatenate(s1, &s2);
 
     println!("s2: {s2}, s3: {s3}");
 
-    // The type of &s2 is &String, not &str, as specified in the second parameter to add. So why does it compile?
-
-    // Because compiler is smart. Compiler can coerce the &String argument into a &str. When we call the add method, Rust uses a deref coercion, which here turns &s2 into &s2[..]
-
-    // For concatening multiple string it's better to use format! macro
-    let s1 = String::from("tic");
-    let s2 = String::from("tac");
-    let s3 = String::from("toe");
-
-    let s = format!("{s1}-{s2}-{s3}");
+    // For concatening multiple string it's better to use format! macro, see
+    // dashed
+    let s = dashed("tic", "tac", "toe");
     println!("format macro output: {s}");
 
 
@@ -83,19 +75,45 @@ pub fn chapter_8_strings()
     // we will again use chars() method to get an iterator over chars
     // then we will use map() and lambda expr that for each character will 
     // change it based on our rules
-    let s: String = "Hello, world!"
-                    .chars()
-                    .map(|x| match x {
-                        '!' => '#',
-                        ',' => '%',
-                        'A'..='Z' => 'X',
-                        _ => x
-                    })
-                    .collect(); // transforms iterator into collection
+    let s = change_chars("Hello, world!");
     println!("String before : Hello, world!");
     println!("String changed: {s}");
 }
 
+#[allow(clippy::ptr_arg)]
+fn concatenate(s1: String, s2: &String) -> String
+{
+    // we need a reference to &s2 since '+' in real is an add function that 
+    // has signature: 
+    //
+    // fn add(self, s: &str) -> String { 
+    //
+    // so s1 ownership is taken by the result, while s2 will still exist 
+    s1 + s2
+
+    // The type of s2 is &String, not &str, as specified in the second parameter to add. So why does it compile?
+
+    // Because compiler is smart. Compiler can coerce the &String argument into a &str. When we call the add method, Rust uses a deref coercion, which here turns s2 into &s2[..]
+}
+
+fn dashed(s1: &str, s2: &str, s3: &str) -> String
+{
+    format!("{s1}-{s2}-{s3}")
+}
+
+fn change_chars(s: &str) -> String
+{
+    s.chars()
+        .map(|x| match x {
+            '!' => '#',
+            ',' => '%',
+            'A'..='Z' => 'X',
+            _ => x
+        })
+        .collect() // transforms iterator into collection
+}
+
+#[allow(clippy::useless_vec, clippy::vec_init_then_push)]
 pub fn chapter_8_vectors()
 {
 
@@ -136,21 +154,11 @@ pub fn chapter_8_vectors()
 
     // best way of getting values from vector is: get()
     // we get an option of &String
-    let second_val: Option<&String> = v_strings.get(22);
-
-    match second_val
-    {
-        Some(val) => println!("second val using get is: {val}"),
-        None => println!("INDEX OUT OF SOCPE using get")
-    }
+    // (see describe_get below this function)
+    println!("{}", describe_get(&v_strings, 22));
+    println!("{}", describe_get(&v_strings, 1));
     let second_val: Option<&String> = v_strings.get(1);
 
-    match second_val
-    {
-        Some(val) => println!("second val using get is: {val}"),
-        None => println!("INDEX OUT OF SOCPE using get")
-    }
-
 
     // we can also do this using let else
     let Some(val) = second_val else {
@@ -175,14 +183,57 @@ pub fn chapter_8_vectors()
 
     // mutable forr looping
 
-    for elem in &mut v
+    set_all_to_69(&mut v);
+    println!("vector after we changed it in loop: {v:?}");
+
+    // Dropping a Vector Drops Its Elements
+}
+
+fn describe_get(v: &[String], idx: usize) -> String
+{
+    // we get an option of &String
+    let val: Option<&String> = v.get(idx);
+
+    match val
+    {
+        Some(val) => format!("second val using get is: {val}"),
+        None => String::from("INDEX OUT OF SOCPE using get")
+    }
+}
+
+fn set_all_to_69(v: &mut Vec<i32>)
+{
+    for elem in v
     {
         // elem is a reference to the value stored in v
         // this if we want to change value the reference points to, like with
         // pointers in C, we need to dereference it using '*'
         *elem = 69;
     }
-    println!("vector after we changed it in loop: {v:?}");
+}
 
-    // Dropping a Vector Drops Its Elements
-}
\ No newline at end of file
+// ############################## CHECKS ##################################
+
+pub fn chapter_8_vectors_checks() -> Vec<Check>
+{
+    let v_strings = vec![String::from("a"), String::from("asd"), String::from("xdxd")];
+    let mut changed = vec![100, 32, 57];
+    set_all_to_69(&mut changed);
+
+    vec![
+        Check::new("get in bounds", String::from("second val using get is: asd"), describe_get(&v_strings, 1)),
+        Check::new("get out of bounds", String::from("INDEX OUT OF SOCPE using get"), describe_get(&v_strings, 22)),
+        Check::new("changed through &mut", vec![69, 69, 69], changed),
+    ]
+}
+
+pub fn chapter_8_strings_checks() -> Vec<Check>
+{
+    let s2 = String::from("world!");
+
+    vec![
+        Check::new("+ concatenation", "Hello, world!", concatenate(String::from("Hello, "), &s2).as_str()),
+        Check::new("format! concatenation", "tic-tac-toe", dashed("tic", "tac", "toe").as_str()),
+        Check::new("map over chars", "Xello% world#", change_chars("Hello, world!").as_str()),
+    ]
+}
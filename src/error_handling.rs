@@ -1,5 +1,7 @@
 // RUST DOES NOT HAVE EXCEPTIONS
 
+#![allow(unused, non_snake_case)]
+
 use std::fs::File;
 use std::io::{self, Read};
 
@@ -44,6 +46,8 @@ pub fn chapter_9_recoverable_errors() {
 
     // instead of panicking we return Result and propagate Err
     // We don’t have enough information on what the calling code is actually trying to do, so we propagate all the success or error information upward for it to handle appropriately
+    // the long form that ? shortens, further down
+    #[allow(clippy::question_mark)]
     fn read_username_from_file() -> Result<String, io::Error> 
     {
         let mut file = match File::open("hello.txt") {
@@ -95,7 +99,7 @@ pub fn chapter_9_recoverable_errors() {
     }
 }
 
-fn chapter_9_unrecoverable_errors() {
+pub fn chapter_9_unrecoverable_errors() {
     // unrecoverable errors are done by using panic! macro
     panic!("Programme panicked and ended execution");
 }
@@ -1,7 +1,89 @@
+#![allow(unused, non_snake_case)]
+
 use std::cmp;
 use std::fmt::Display;
 use std::fmt::Debug;
 
+use crate::checks::Check;
+use crate::geometry;
+
+// The Summary trait of chapter 10 (see TRAITS in chapter_10_generics) and
+// its two implementations. They live out here so the library can use them
+// as well.
+
+// implementing traits
+// - trait name: Summary
+// - trait func: summarize --> every struct that will have trait Summary
+//                             will have to have 'summarize' method impl
+pub trait Summary
+{
+    // we declare method signatures, we can also add default impl of it
+    // fn summarize(&self) -> String;
+    fn summarize(&self) -> String
+    {
+        format!("(Read more), author: {}", self.summarize_author())
+    }
+
+    // Default implementations can call other methods in the same trait, even if those other methods don’t have a default implementation.
+    fn summarize_author(&self) -> String;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewsArticle
+{
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+}
+
+// To implement a trait for given struct:
+// -- after impl we put trait name
+// -- then we put 'for struct_name' since we want to impl it for struct
+impl Summary for NewsArticle
+{
+    fn summarize(&self) -> String
+    {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+
+    fn summarize_author(&self) -> String
+    {
+        format!("@{}", self.author)
+    }
+}
+// if we want to use default implementation of some of traits methods
+// we just don't impl them in our impl, in this case we would do it
+// like that:
+// impl Summary for NewsArticle {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocialPost
+{
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub repost: bool,
+}
+
+impl Summary for SocialPost
+{
+    fn summarize(&self) -> String
+    {
+        format!("{}: {}", self.username, self.content)
+    }
+
+    fn summarize_author(&self) -> String
+    {
+        format!("SocialPost auth: @{}", self.username)
+    }
+}
+
+pub fn notify(item: &impl Summary) -> String
+{
+    format!("Breaking news! {}", item.summarize())
+}
+
 pub fn chapter_10_lifetimes()
 {
     // Lifetimes are another kind of generic that we’ve already been using. Rather than ensuring that a type has the behavior we want, lifetimes ensure that references are valid as long as we need them to be.
@@ -86,11 +168,10 @@ pub fn chapter_10_lifetimes()
         //
         //  We place lifetime parameter annotations after the &
 
-        // To use lifetime annotations in function signatures, we need to below:
-        fn longest<'a>(x: &'a str, y: &'a str) -> &'a str 
-        {
-            if x.len() > y.len() { x } else { y }
-        }
+        // To use lifetime annotations in function signatures, we need to
+        // write it like longest below chapter_10_lifetimes:
+        //
+        // fn longest<'a>(x: &'a str, y: &'a str) -> &'a str
 
         // !!!!!!!!!!!!!!!!!!
         // The function signature now tells Rust that for some lifetime 'a, the function takes two parameters, both of which are string slices that LIVE AT LEAST AS LONG AS LIFETIME 'a. 
@@ -199,6 +280,65 @@ pub fn chapter_10_lifetimes()
 
 }
 
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str
+{
+    if x.len() > y.len() { x } else { y }
+}
+
+// we need to add to type T trait that T needs to know how to compare
+fn largest_generic<T: cmp::PartialOrd>(lst: &[T]) -> &T
+{
+    let mut largest = &lst[0];
+
+    for item in lst
+    {
+        if item > largest
+        {
+            largest = item;
+        }
+    }
+
+    largest
+}
+
+// The Point of the STRUCTS part of chapter_10_generics, with its impl
+// blocks, out here so the checks can call its methods too
+
+struct Point<T, U>
+{
+    x: T,
+    y: U
+}
+
+// implementation of generic methods for structs
+
+// We have to declare T just after impl so we can use T to specify that we’re implementing methods on the type Point<T>. By declaring T as a generic type after impl, Rust can identify that the type in the angle brackets in Point is a generic type rather than a concrete type
+impl <T, U> Point<T, U> 
+{
+    fn x(&self) -> &T
+    {
+        &self.x
+    }
+
+    fn y(&self) -> &U
+    {
+        &self.y
+    }
+}
+
+// Using generics does not slow down your programme at runtime
+// (it does make compilation longer though)
+// We can define methods only for some types
+impl Point<f32, f32>
+{
+    // below function will be avaialbe only for Point<T, U> where 
+    // T = U = f32
+    fn dist_from_origin(&self) -> f32
+    {
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
+    }
+}
+
 pub fn chapter_10_generics()
 {
     // FUNCTIONS
@@ -230,65 +370,28 @@ pub fn chapter_10_generics()
             largest
         }
 
-        // WITH GENERICS
-        // we need to add to type T trait that T needs to know how to compare
-        fn largest_generic<T: cmp::PartialOrd>(lst: &[T]) -> &T
-        {
-            let mut largest = &lst[0];
-
-            for item in lst
-            {
-                if item > largest
-                {
-                    largest = item;
-                }
-            }
-
-            largest
-        }
+        // WITH GENERICS, see largest_generic below this function, one
+        // function for both
+        println!("largest i32: {}", largest_generic(&[34, 50, 25, 100, 65]));
+        println!("largest char: {}", largest_generic(&['y', 'm', 'a', 'q']));
     }
 
     // STRUCTS
     {
-        struct Point<T, U>
-        {
-            x: T,
-            y: U
-        }
+        // Point<T, U> and its impl blocks are above this function, so the
+        // checks can use them too
 
         let integer = Point { x: 5, y: 10 };
         let float = Point { x: 1.0, y: 4.0 };
         let mixed = Point {x: 1.5, y: 69};
 
-        // implementation of generic methods for structs
+        println!("integer.x = {}, mixed.y = {}", integer.x(), mixed.y());
+        let float_f32 = Point { x: 1.0f32, y: 4.0f32 };
+        println!("float_f32 is {} from the origin", float_f32.dist_from_origin());
 
-        // We have to declare T just after impl so we can use T to specify that we’re implementing methods on the type Point<T>. By declaring T as a generic type after impl, Rust can identify that the type in the angle brackets in Point is a generic type rather than a concrete type
-        impl <T, U> Point<T, U> 
-        {
-            fn x(&self) -> &T
-            {
-                &self.x
-            }
-
-            fn y(&self) -> &U
-            {
-                &self.y
-            }
-        }
-
-        // Using generics does not slow down your programme at runtime
-        // (it does make compilation longer though)
-        // We can define methods only for some types
-        impl Point<f32, f32>
-        {
-            // below function will be avaialbe only for Point<T, U> where 
-            // T = U = f32
-            fn dist_from_origin(&self) -> f32
-            {
-                (self.x.powi(2) + self.y.powi(2)).sqrt()
-            }
-        }
-            
+        // The library's points, geometry::Point<T> in src/geometry/point.rs,
+        // grew out of this one. They have a single T so they can be added
+        // and subtracted, and dist_from_origin works for every number type.
     }
 
     // TRAITS
@@ -301,71 +404,8 @@ pub fn chapter_10_generics()
 
         // Trait definitions are a way to group method signatures together to define a set of behaviors necessary to accomplish some purpose
 
-        // implementing traits
-        // - trait name: Summary
-        // - trait func: summarize --> every struct that will have trait Summary
-        //                             will have to have 'summarize' method impl
-        pub trait Summary
-        {
-            // we declare method signatures, we can also add default impl of it 
-            // fn summarize(&self) -> String;
-            fn summarize(&self) -> String
-            {
-                format!("(Read more), author: {}", self.summarize_author())
-            }
-
-            // Default implementations can call other methods in the same trait, even if those other methods don’t have a default implementation. 
-            fn summarize_author(&self) -> String;
-        }
-
-        pub struct NewsArticle
-        {
-            pub headline: String,
-            pub location: String,
-            pub author: String,
-            pub content: String
-        }
-
-        // To implement a trait for given struct:
-        // -- after impl we put trait name
-        // -- then we put 'for struct_name' since we want to impl it for struct 
-        impl Summary for NewsArticle
-        {
-            fn summarize(&self) -> String {
-                format!("{}, by {} ({})", self.headline, self.author, self.location)
-            }
-
-            fn summarize_author(&self) -> String {
-                format!("@{}", self.author)
-            }
-        }
-        // if we want to use default implementation of some of traits methods
-        // we just don't impl them in our impl, in this case we would do it 
-        // like that:
-        // impl Summary for NewsArticle {}
-
-
-        pub struct SocialPost 
-        {
-            pub username: String,
-            pub content: String,
-            pub reply: bool,
-            pub repost: bool,
-        }
-
-        impl Summary for SocialPost
-        {
-            fn summarize(&self) -> String {
-                format!("{}: {}", self.username, self.content)
-            }
-
-            fn summarize_author(&self) -> String {
-                format!("SocialPost auth: @{}", self.username)
-            }
-        }
-
+        // Summary, NewsArticle and SocialPost are at the top of this file
 
-        
         let post = SocialPost {
             username: String::from("horse_ebooks"),
             content: String::from(
@@ -380,16 +420,6 @@ pub fn chapter_10_generics()
 
     // Traits as PARAMETERS
     {
-        pub trait Summary
-        {
-            fn summarize(&self) -> String
-            {
-                format!("(Read more), author: {}", self.summarize_author())
-            }
-
-            fn summarize_author(&self) -> String;
-        }
-
         // Instead of a concrete type for the item parameter, we specify the impl keyword and the trait name. So we accept any type that implements Summary trait
         pub fn notfiy(item: &impl Summary)  // SYNTAX SUGAR
         {
@@ -495,3 +525,53 @@ pub fn chapter_10_generics()
         
     }
 }
+
+// ############################## CHECKS ##################################
+
+pub fn chapter_10_generics_checks() -> Vec<Check>
+{
+
+    let post = SocialPost {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        repost: false,
+    };
+    let mixed = Point { x: 1.5, y: 69 };
+
+    vec![
+        Check::new("largest number", &100, largest_generic(&[34, 50, 25, 100, 65])),
+        Check::new("largest char", &'y', largest_generic(&['y', 'm', 'a', 'q'])),
+        Check::new("trait method", "horse_ebooks: of course, as you probably already know, people", post.summarize().as_str()),
+        Check::new("default trait method", "(Read more), author: SocialPost auth: @horse_ebooks", Summary::summarize(&OnlyAuthor(&post)).as_str()),
+        Check::new("generic point getters", (&1.5, &69), (mixed.x(), mixed.y())),
+        Check::new("distance for f32 points", 5.0, Point { x: 3.0f32, y: 4.0 }.dist_from_origin()),
+        Check::new("library points have it for integers too", 5.0, geometry::Point::new(3u32, 4).dist_from_origin()),
+    ]
+}
+
+// Uses the default summarize, only summarize_author is implemented
+struct OnlyAuthor<'a>(&'a SocialPost);
+
+impl Summary for OnlyAuthor<'_>
+{
+    fn summarize_author(&self) -> String
+    {
+        self.0.summarize_author()
+    }
+}
+
+pub fn chapter_10_lifetimes_checks() -> Vec<Check>
+{
+    let string1 = String::from("long string is long");
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = longest(string1.as_str(), string2.as_str()).to_string();
+    }
+
+    vec![
+        Check::new("longest of two", "long string is long", result.as_str()),
+        Check::new("ties go to the second", "def", longest("abc", "def")),
+    ]
+}
@@ -0,0 +1,232 @@
+// The Rectangle from chapter 5, outside of the lesson so other code can
+// build on it. It's generic over the Scalar of its position and of its sides.
+// Plain Rectangle, i32 position and u32 sides, is made of whole cells and
+// has the methods below. Rect<T> is Rectangle<T, T>, position and sides of
+// the same type, with the maths of rect.rs: float coordinates, centers,
+// scaling.
+//
+// A rectangle covers the cells x..x+width and y..y+height, so the right and
+// bottom edges are not part of it and two rectangles sharing an edge don't
+// intersect. Edges are computed as i64: x + width can't overflow there, no
+// matter how far from the origin the rectangle is.
+
+use std::fmt;
+use std::str::FromStr;
+
+mod cluster;
+mod csv;
+mod hull;
+mod index;
+mod kdtree;
+mod packing;
+mod point;
+mod polygon;
+mod predicates;
+mod rect;
+mod render;
+mod shapes;
+mod transform;
+
+pub use cluster::{dbscan, kmeans, Clustering, DbscanConfig, KMeansConfig};
+pub use csv::{load_points_csv, parse_points_csv, CsvError};
+pub use hull::{closest_pair, convex_hull};
+pub use index::{ItemId, SpatialIndex};
+pub use kdtree::{Chebyshev, Euclidean, KdPoint, KdTree, Manhattan, Metric, Neighbour};
+pub use packing::{pack, Heuristic, PackConfig, Packing, Placement};
+pub use point::{Point, Point3, Scalar};
+pub use polygon::{Containment, Polygon, Segment, SegmentIntersection};
+pub use predicates::{orientation, Orientation};
+pub use rect::Rect;
+pub use render::{Scene, Shape};
+pub use shapes::{load_shapes, parse_shapes, NamedRect, ShapesError};
+pub use transform::Transform;
+
+// P is the type of the position, S of the sides
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rectangle<P = i32, S = u32>
+{
+    pub x: P,
+    pub y: P,
+    pub width: S,
+    pub height: S,
+}
+
+impl Rectangle
+{
+    // A rectangle at the origin, like the ones in the book
+    pub fn new(width: u32, height: u32) -> Self
+    {
+        Rectangle { x: 0, y: 0, width, height }
+    }
+
+    pub fn at(x: i32, y: i32, width: u32, height: u32) -> Self
+    {
+        Rectangle { x, y, width, height }
+    }
+
+    // associated function that isn't a method, called with Rectangle::square.
+    // Self is an alias for the type that appears after the impl keyword.
+    pub fn square(size: u32) -> Self
+    {
+        Self::new(size, size)
+    }
+
+    // Tries to build the rectangle spanning left..right and top..bottom,
+    // None if it doesn't fit in the field types
+    pub fn from_edges(left: i64, top: i64, right: i64, bottom: i64) -> Option<Self>
+    {
+        Some(Rectangle {
+            x: i32::try_from(left).ok()?,
+            y: i32::try_from(top).ok()?,
+            width: u32::try_from(right.checked_sub(left)?).ok()?,
+            height: u32::try_from(bottom.checked_sub(top)?).ok()?,
+        })
+    }
+
+    pub fn left(&self) -> i64
+    {
+        i64::from(self.x)
+    }
+
+    pub fn top(&self) -> i64
+    {
+        i64::from(self.y)
+    }
+
+    pub fn right(&self) -> i64
+    {
+        self.left() + i64::from(self.width)
+    }
+
+    pub fn bottom(&self) -> i64
+    {
+        self.top() + i64::from(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.width == 0 || self.height == 0
+    }
+
+    // u64, because u32::MAX * u32::MAX doesn't fit in u32
+    pub fn area(&self) -> u64
+    {
+        u64::from(self.width) * u64::from(self.height)
+    }
+
+    // How much bigger self is than `other`, None if `other` is bigger. u64
+    // can't go below 0 either, plain '-' panics in debug builds (and wraps in
+    // release) when `other` is the bigger one, checked_sub gives None instead
+    pub fn checked_area_diff(&self, other: &Rectangle) -> Option<u64>
+    {
+        self.area().checked_sub(other.area())
+    }
+
+    // Moves the rectangle, None if it would leave the i32 plane
+    pub fn checked_translate(&self, dx: i32, dy: i32) -> Option<Self>
+    {
+        Some(Rectangle { x: self.x.checked_add(dx)?, y: self.y.checked_add(dy)?, ..*self })
+    }
+
+    // true if self is bigger than `other` in both sides, wherever they are,
+    // so a rectangle can't hold one of its own size. The can_hold of the book.
+    pub fn can_hold(&self, other: &Rectangle) -> bool
+    {
+        self.width > other.width && self.height > other.height
+    }
+
+    // true if `other` lies completely inside self, touching edges included
+    pub fn contains_rect(&self, other: &Rectangle) -> bool
+    {
+        self.left() <= other.left()
+            && self.top() <= other.top()
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    pub fn contains_point(&self, x: i32, y: i32) -> bool
+    {
+        let (x, y) = (i64::from(x), i64::from(y));
+        self.left() <= x && x < self.right() && self.top() <= y && y < self.bottom()
+    }
+
+    // true if the rectangles share some area, not just an edge
+    pub fn intersects(&self, other: &Rectangle) -> bool
+    {
+        self.intersection(other).is_some()
+    }
+
+    // The area both rectangles cover, None if they don't overlap
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle>
+    {
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        if left < right && top < bottom
+        {
+            Rectangle::from_edges(left, top, right, bottom)
+        }
+        else
+        {
+            None
+        }
+    }
+
+    // The smallest rectangle holding both of them. None when it's too big for
+    // u32 sides, e.g. for rectangles at i32::MIN and i32::MAX
+    pub fn union_bounds(&self, other: &Rectangle) -> Option<Rectangle>
+    {
+        Rectangle::from_edges(
+            self.left().min(other.left()),
+            self.top().min(other.top()),
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        )
+    }
+}
+
+// "30x50" at the origin, "30x50@10,20" anywhere else
+impl fmt::Display for Rectangle
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}x{}", self.width, self.height)?;
+        if (self.x, self.y) != (0, 0)
+        {
+            write!(f, "@{},{}", self.x, self.y)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Rectangle
+{
+    type Err = String;
+
+    // Reads what Display writes, "30x50" or "30x50@10,20"
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let invalid = || format!("invalid rectangle '{s}', expected WIDTHxHEIGHT or WIDTHxHEIGHT@X,Y");
+
+        let (size, origin) = match s.trim().split_once('@')
+        {
+            Some((size, origin)) => (size, Some(origin)),
+            None => (s.trim(), None),
+        };
+        let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+        let (x, y) = match origin
+        {
+            Some(origin) => origin.split_once(',').ok_or_else(invalid)?,
+            None => ("0", "0"),
+        };
+
+        Ok(Rectangle::at(
+            x.parse().map_err(|_| invalid())?,
+            y.parse().map_err(|_| invalid())?,
+            width.parse().map_err(|_| invalid())?,
+            height.parse().map_err(|_| invalid())?,
+        ))
+    }
+}
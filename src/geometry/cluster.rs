@@ -0,0 +1,266 @@
+// Grouping points that lie close together.
+//
+// k-means splits the points into k groups around their means, for when
+// the number of groups is known. DBSCAN finds groups of any shape and
+// number as places where points are dense, and leaves the lonely points
+// out as noise.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Euclidean, KdTree, Point};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Clustering
+{
+    // the cluster of every point, in the order given, None for noise (and
+    // for points with a coordinate that isn't a number)
+    pub assignments: Vec<Option<usize>>,
+    // the mean of every cluster's points
+    pub centroids: Vec<Point<f64>>,
+    // sum of the squared distances of the points to their centroid, smaller
+    // is tighter
+    pub inertia: f64,
+}
+
+impl Clustering
+{
+    fn new(points: &[Point<f64>], assignments: Vec<Option<usize>>, clusters: usize) -> Self
+    {
+        let centroids = means(points, &assignments, &vec![Point::origin(); clusters]);
+        let inertia = inertia(points, &assignments, &centroids);
+        Clustering { assignments, centroids, inertia }
+    }
+
+    pub fn cluster_count(&self) -> usize
+    {
+        self.centroids.len()
+    }
+
+    // Indices of the points in `cluster`
+    pub fn members(&self, cluster: usize) -> impl Iterator<Item = usize> + '_
+    {
+        self.assignments.iter().enumerate().filter(move |(_, a)| **a == Some(cluster)).map(|(i, _)| i)
+    }
+
+    pub fn noise(&self) -> impl Iterator<Item = usize> + '_
+    {
+        self.assignments.iter().enumerate().filter(|(_, a)| a.is_none()).map(|(i, _)| i)
+    }
+}
+
+// The mean of every cluster, `previous` for clusters left without points
+fn means(points: &[Point<f64>], assignments: &[Option<usize>], previous: &[Point<f64>]) -> Vec<Point<f64>>
+{
+    let mut sums = vec![(Point::origin(), 0usize); previous.len()];
+    for (p, cluster) in points.iter().zip(assignments)
+    {
+        if let Some(c) = *cluster
+        {
+            sums[c].0 = sums[c].0 + *p;
+            sums[c].1 += 1;
+        }
+    }
+
+    sums.iter()
+        .zip(previous)
+        .map(|(&(sum, count), &old)| if count == 0 { old } else { sum * (1.0 / count as f64) })
+        .collect()
+}
+
+fn inertia(points: &[Point<f64>], assignments: &[Option<usize>], centroids: &[Point<f64>]) -> f64
+{
+    points.iter().zip(assignments).filter_map(|(p, c)| c.map(|c| p.distance_squared(centroids[c]))).sum()
+}
+
+fn is_number(p: &Point<f64>) -> bool
+{
+    p.x.is_finite() && p.y.is_finite()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KMeansConfig
+{
+    pub k: usize,
+    // Lloyd's rounds at most, it usually settles long before
+    pub max_iterations: usize,
+    // the same seed gives the same clusters, None picks one at random
+    pub seed: Option<u64>,
+}
+
+impl KMeansConfig
+{
+    pub fn new(k: usize) -> Self
+    {
+        KMeansConfig { k, max_iterations: 100, seed: None }
+    }
+}
+
+// k-means++: the first centroid is a random point, every next one a point
+// picked with a chance growing with the squared distance to the nearest
+// centroid so far, which spreads them out. Then Lloyd's: every point goes
+// to its nearest centroid and the centroids move to the means, until no
+// point changes cluster.
+//
+// There are fewer than k clusters when there are fewer than k different
+// points.
+pub fn kmeans(points: &[Point<f64>], config: &KMeansConfig) -> Clustering
+{
+    let mut rng = match config.seed
+    {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let valid: Vec<Point<f64>> = points.iter().copied().filter(is_number).collect();
+    let mut centroids = seed_centroids(&valid, config.k, &mut rng);
+    let mut assignments = vec![None; points.len()];
+
+    for _ in 0..config.max_iterations.max(1)
+    {
+        let mut changed = false;
+        for (p, assignment) in points.iter().zip(assignments.iter_mut())
+        {
+            let nearest = if is_number(p) { nearest_centroid(*p, &centroids) } else { None };
+            changed |= nearest != *assignment;
+            *assignment = nearest;
+        }
+
+        if !changed
+        {
+            break;
+        }
+        centroids = means(points, &assignments, &centroids);
+    }
+
+    let inertia = inertia(points, &assignments, &centroids);
+    Clustering { assignments, centroids, inertia }
+}
+
+fn seed_centroids(points: &[Point<f64>], k: usize, rng: &mut StdRng) -> Vec<Point<f64>>
+{
+    let mut centroids = Vec::with_capacity(k);
+    if points.is_empty() || k == 0
+    {
+        return centroids;
+    }
+    centroids.push(points[rng.gen_range(0..points.len())]);
+
+    // squared distance of every point to its nearest centroid so far
+    let mut weights: Vec<f64> = points.iter().map(|p| p.distance_squared(centroids[0])).collect();
+    while centroids.len() < k
+    {
+        let total: f64 = weights.iter().sum();
+        // every point is a centroid already
+        if total <= 0.0
+        {
+            break;
+        }
+
+        let mut target = rng.r#gen::<f64>() * total;
+        let picked = weights
+            .iter()
+            .position(|&w| {
+                target -= w;
+                w > 0.0 && target < 0.0
+            })
+            // rounding can leave a little of target, the last one it is
+            .unwrap_or_else(|| weights.iter().rposition(|&w| w > 0.0).expect("total is above 0"));
+
+        let centroid = points[picked];
+        centroids.push(centroid);
+        for (w, p) in weights.iter_mut().zip(points)
+        {
+            *w = w.min(p.distance_squared(centroid));
+        }
+    }
+
+    centroids
+}
+
+// Of two just as near, the first one
+fn nearest_centroid(p: Point<f64>, centroids: &[Point<f64>]) -> Option<usize>
+{
+    centroids
+        .iter()
+        .map(|c| p.distance_squared(*c))
+        .enumerate()
+        .min_by(|(i, a), (j, b)| a.total_cmp(b).then(i.cmp(j)))
+        .map(|(i, _)| i)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DbscanConfig
+{
+    // how close two points must be to be neighbours, inclusive
+    pub epsilon: f64,
+    // neighbours (the point itself included) a point needs to start or
+    // grow a cluster
+    pub min_points: usize,
+}
+
+// A point with at least min_points neighbours is a core point. Core points
+// that are neighbours are in the same cluster, together with every point
+// next to one of them. The rest is noise. Clusters are numbered in the
+// order their first core point comes in `points`; a point next to core
+// points of two clusters goes to the one found first.
+pub fn dbscan(points: &[Point<f64>], config: &DbscanConfig) -> Clustering
+{
+    let valid: Vec<usize> = (0..points.len()).filter(|&i| is_number(&points[i])).collect();
+    let valid_points: Vec<Point<f64>> = valid.iter().map(|&i| points[i]).collect();
+    let tree = KdTree::build(&valid_points);
+
+    // indices into `valid` from here on
+    let neighbours = |i: usize| -> Vec<usize> { tree.within_radius(&valid_points[i], config.epsilon, &Euclidean).into_iter().map(|n| n.index).collect() };
+
+    let mut clusters: Vec<Option<usize>> = vec![None; valid.len()];
+    let mut visited = vec![false; valid.len()];
+    let mut count = 0;
+
+    for start in 0..valid.len()
+    {
+        if visited[start]
+        {
+            continue;
+        }
+        visited[start] = true;
+
+        let around = neighbours(start);
+        if around.len() < config.min_points
+        {
+            // noise, unless a core point reaches it later
+            continue;
+        }
+
+        let cluster = count;
+        count += 1;
+        clusters[start] = Some(cluster);
+
+        let mut queue = around;
+        while let Some(i) = queue.pop()
+        {
+            if clusters[i].is_none()
+            {
+                clusters[i] = Some(cluster);
+            }
+            if visited[i]
+            {
+                continue;
+            }
+            visited[i] = true;
+
+            let around = neighbours(i);
+            if around.len() >= config.min_points
+            {
+                queue.extend(around);
+            }
+        }
+    }
+
+    let mut assignments = vec![None; points.len()];
+    for (&i, cluster) in valid.iter().zip(clusters)
+    {
+        assignments[i] = cluster;
+    }
+    Clustering::new(points, assignments, count)
+}
@@ -0,0 +1,128 @@
+// Points from CSV, one per line:
+//
+//   name,lon,lat          optional header
+//   home,21.01,52.23
+//
+// Points are always x = longitude, y = latitude. Without a header the
+// coordinates are the first two columns holding numbers on the first line,
+// in that order: x (lon) then y (lat), like GeoJSON. A file with the
+// latitude first needs a header saying so.
+//
+// The first line is a header when it names the columns or has no number in
+// it. With a header the coordinates are taken from the columns named x and
+// y, or lon (lng, longitude) and lat (latitude), in any case, and when no
+// column has those names from the first two columns. Other columns are
+// ignored, blank lines are skipped. Fields may be quoted, but quotes can't
+// hide a comma.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::Point;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvError
+{
+    // the file, or "<input>" for text that didn't come from one
+    pub origin: String,
+    // counts from 1, 0 for errors about the whole file
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CsvError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        if self.line == 0
+        {
+            return write!(f, "{}: {}", self.origin, self.message);
+        }
+        write!(f, "{}:{}: {}", self.origin, self.line, self.message)
+    }
+}
+
+impl Error for CsvError {}
+
+fn fields(line: &str) -> Vec<&str>
+{
+    line.split(',').map(|field| field.trim().trim_matches('"').trim()).collect()
+}
+
+// Which columns hold x and y, if the header names them
+fn named_columns(header: &[&str]) -> Option<(usize, usize)>
+{
+    let find = |names: &[&str]| header.iter().position(|field| names.iter().any(|name| field.eq_ignore_ascii_case(name)));
+    let x = find(&["x", "lon", "lng", "longitude"])?;
+    let y = find(&["y", "lat", "latitude"])?;
+    Some((x, y))
+}
+
+fn parse(text: &str, origin: &str) -> Result<Vec<Point<f64>>, CsvError>
+{
+    let error = |line: usize, message: String| CsvError { origin: origin.to_string(), line, message };
+    let mut columns = None;
+    let mut points = Vec::new();
+
+    for (i, line) in text.lines().enumerate()
+    {
+        if line.trim().is_empty()
+        {
+            continue;
+        }
+        let fields = fields(line);
+
+        let Some((x, y)) = columns else {
+            if let Some(named) = named_columns(&fields)
+            {
+                columns = Some(named);
+                continue;
+            }
+            let numbers: Vec<usize> = (0..fields.len()).filter(|&column| fields[column].parse::<f64>().is_ok()).collect();
+            let (x, y) = match numbers[..]
+            {
+                [x, y, ..] => (x, y),
+                _ => (0, 1),
+            };
+            columns = Some((x, y));
+            // a header without the names, nothing to read on it
+            if numbers.is_empty()
+            {
+                continue;
+            }
+            points.push(point(&fields, x, y).map_err(|message| error(i + 1, message))?);
+            continue;
+        };
+
+        points.push(point(&fields, x, y).map_err(|message| error(i + 1, message))?);
+    }
+
+    Ok(points)
+}
+
+fn point(fields: &[&str], x: usize, y: usize) -> Result<Point<f64>, String>
+{
+    let coordinate = |column: usize| -> Result<f64, String> {
+        let field = fields.get(column).ok_or_else(|| format!("expected at least {} columns, found {}", x.max(y) + 1, fields.len()))?;
+        match field.parse::<f64>()
+        {
+            Ok(value) if value.is_finite() => Ok(value),
+            _ => Err(format!("'{field}' in column {} is not a number", column + 1)),
+        }
+    };
+    Ok(Point::new(coordinate(x)?, coordinate(y)?))
+}
+
+pub fn parse_points_csv(text: &str) -> Result<Vec<Point<f64>>, CsvError>
+{
+    parse(text, "<input>")
+}
+
+pub fn load_points_csv(path: &Path) -> Result<Vec<Point<f64>>, CsvError>
+{
+    let origin = path.display().to_string();
+    let text = fs::read_to_string(path).map_err(|e| CsvError { origin: origin.clone(), line: 0, message: format!("can't read: {e}") })?;
+    parse(&text, &origin)
+}
@@ -0,0 +1,137 @@
+// Algorithms over sets of points: the convex hull and the closest pair.
+// Points with a NaN coordinate are left out, they have no place anywhere.
+
+use super::point::by_x_then_y;
+use super::predicates::{orientation, Orientation};
+use super::{Point, Polygon, Scalar};
+
+fn is_number<T: Scalar>(p: &Point<T>) -> bool
+{
+    // NaN is the only value that can't be compared, not even to itself
+    p.x.partial_cmp(&p.x).is_some() && p.y.partial_cmp(&p.y).is_some()
+}
+
+// The smallest convex polygon holding all the points, counter clockwise
+// (with y going up) from the leftmost bottom point, without collinear
+// points on its edges. Andrew's monotone chain: the points are sorted left
+// to right, then the lower and the upper half are built by dropping every
+// point the hull would turn clockwise at.
+pub fn convex_hull<T: Scalar>(points: &[Point<T>]) -> Polygon<T>
+{
+    let mut sorted: Vec<Point<T>> = points.iter().copied().filter(is_number).collect();
+    sorted.sort_by(by_x_then_y);
+    sorted.dedup();
+
+    if sorted.len() < 3
+    {
+        return Polygon::new(sorted);
+    }
+
+    let mut hull: Vec<Point<T>> = Vec::with_capacity(sorted.len() + 1);
+    let half = |hull: &mut Vec<Point<T>>, points: &mut dyn Iterator<Item = &Point<T>>, floor: usize| {
+        for &p in points
+        {
+            while hull.len() >= floor + 2 && orientation(hull[hull.len() - 2], hull[hull.len() - 1], p) != Orientation::CounterClockwise
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+    };
+
+    half(&mut hull, &mut sorted.iter(), 0);
+    // the upper half can't pop what the lower one found, only add to it
+    let lower = hull.len() - 1;
+    half(&mut hull, &mut sorted.iter().rev().skip(1), lower);
+
+    // the last point is the first one again
+    hull.pop();
+    Polygon::new(hull)
+}
+
+// The two points closest to each other, None for less than two points.
+// Divide and conquer: the closest pair is in the left half, in the right
+// half, or in the strip along the line between them, where every point
+// only has to be compared with the few above it.
+pub fn closest_pair<T: Scalar>(points: &[Point<T>]) -> Option<(Point<T>, Point<T>)>
+{
+    let mut sorted: Vec<Point<T>> = points.iter().copied().filter(is_number).collect();
+    sorted.sort_by(by_x_then_y);
+
+    let mut best = None;
+    let mut buffer = Vec::with_capacity(sorted.len());
+    closest_in(&mut sorted, &mut buffer, &mut best);
+    best.map(|(_, a, b)| (a, b))
+}
+
+type Best<T> = Option<(f64, Point<T>, Point<T>)>;
+
+fn update<T: Scalar>(best: &mut Best<T>, a: Point<T>, b: Point<T>)
+{
+    let distance = a.distance_squared(b);
+    if best.is_none_or(|(d, _, _)| distance < d)
+    {
+        *best = Some((distance, a, b));
+    }
+}
+
+// Expects the points sorted by x and leaves them sorted by y
+fn closest_in<T: Scalar>(points: &mut [Point<T>], buffer: &mut Vec<Point<T>>, best: &mut Best<T>)
+{
+    let by_y = |p: &Point<T>, q: &Point<T>| p.y.partial_cmp(&q.y).unwrap_or(std::cmp::Ordering::Equal);
+
+    if points.len() <= 3
+    {
+        for i in 0..points.len()
+        {
+            for j in i + 1..points.len()
+            {
+                update(best, points[i], points[j]);
+            }
+        }
+        points.sort_by(by_y);
+        return;
+    }
+
+    let middle = points.len() / 2;
+    let middle_x = points[middle].x.to_f64();
+    let (left, right) = points.split_at_mut(middle);
+    closest_in(left, buffer, best);
+    closest_in(right, buffer, best);
+
+    // merge the halves back into one list sorted by y
+    buffer.clear();
+    let (mut i, mut j) = (0, middle);
+    while i < middle || j < points.len()
+    {
+        if j == points.len() || (i < middle && by_y(&points[i], &points[j]).is_le())
+        {
+            buffer.push(points[i]);
+            i += 1;
+        }
+        else
+        {
+            buffer.push(points[j]);
+            j += 1;
+        }
+    }
+    points.copy_from_slice(buffer);
+
+    // points closer to the middle line than the best distance, bottom to top
+    let limit = best.map_or(f64::INFINITY, |(d, _, _)| d);
+    buffer.clear();
+    buffer.extend(points.iter().filter(|p| (p.x.to_f64() - middle_x).powi(2) < limit));
+
+    for (i, &p) in buffer.iter().enumerate()
+    {
+        for &q in &buffer[i + 1..]
+        {
+            let limit = best.map_or(f64::INFINITY, |(d, _, _)| d);
+            if (q.y.to_f64() - p.y.to_f64()).powi(2) >= limit
+            {
+                break;
+            }
+            update(best, p, q);
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// A quadtree over positioned rectangles, so "what overlaps this region" and
+// "what is under this point" don't have to look at every rectangle.
+//
+// Every node covers a square of the plane and is split into four quadrants
+// once it holds too many rectangles. A rectangle lives in the smallest node
+// that holds it completely, so rectangles lying across a split line stay in
+// the bigger node above. The root covers every rectangle a Rectangle can
+// describe, which is why bounds are i64 here. Once removals bring a split
+// node back down to a leaf's worth of rectangles, its quadrants are merged
+// back into it.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::Rectangle;
+
+// more rectangles than this in a leaf and it gets split
+const NODE_CAPACITY: usize = 8;
+// past this depth the squares are a few cells wide, splitting won't help
+const MAX_DEPTH: u32 = 28;
+
+// Handed out by insert, it's how a rectangle is looked up and removed.
+// The id of a removed rectangle is handed out again by a later insert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId(usize);
+
+impl ItemId
+{
+    pub fn index(self) -> usize
+    {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bounds
+{
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Bounds
+{
+    fn holds(&self, rect: &Rectangle) -> bool
+    {
+        self.left <= rect.left() && self.top <= rect.top() && rect.right() <= self.right && rect.bottom() <= self.bottom
+    }
+
+    fn overlaps(&self, rect: &Rectangle) -> bool
+    {
+        self.left < rect.right() && rect.left() < self.right && self.top < rect.bottom() && rect.top() < self.bottom
+    }
+
+    fn contains_point(&self, x: i64, y: i64) -> bool
+    {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    fn quadrants(&self) -> [Bounds; 4]
+    {
+        let mid_x = self.left + (self.right - self.left) / 2;
+        let mid_y = self.top + (self.bottom - self.top) / 2;
+
+        [
+            Bounds { right: mid_x, bottom: mid_y, ..*self },
+            Bounds { left: mid_x, bottom: mid_y, ..*self },
+            Bounds { right: mid_x, top: mid_y, ..*self },
+            Bounds { left: mid_x, top: mid_y, ..*self },
+        ]
+    }
+}
+
+// Squared distance from a point to the closest point of the box, 0 inside.
+// Sides can be 2^33 long, squared that's too much for i64
+fn distance_squared(x: i64, y: i64, left: i64, top: i64, right: i64, bottom: i64) -> u128
+{
+    let dx = (left - x).max(x - right).max(0).unsigned_abs();
+    let dy = (top - y).max(y - bottom).max(0).unsigned_abs();
+    u128::from(dx) * u128::from(dx) + u128::from(dy) * u128::from(dy)
+}
+
+#[derive(Debug, Clone)]
+struct Node
+{
+    bounds: Bounds,
+    items: Vec<ItemId>,
+    children: Option<Box<[Node; 4]>>,
+    // rectangles in this node and everything below it
+    len: usize,
+}
+
+impl Node
+{
+    fn new(bounds: Bounds) -> Self
+    {
+        Node { bounds, items: Vec::new(), children: None, len: 0 }
+    }
+
+    // The quadrant `rect` fits in completely, if there is one
+    fn child_for(&mut self, rect: &Rectangle) -> Option<&mut Node>
+    {
+        self.children.as_mut()?.iter_mut().find(|child| child.bounds.holds(rect))
+    }
+
+    fn insert(&mut self, id: ItemId, rects: &[Option<Rectangle>], depth: u32)
+    {
+        let rect = rects[id.0].expect("inserted rectangles are stored");
+        self.len += 1;
+        if let Some(child) = self.child_for(&rect)
+        {
+            return child.insert(id, rects, depth + 1);
+        }
+
+        self.items.push(id);
+        if self.children.is_none() && self.items.len() > NODE_CAPACITY && depth < MAX_DEPTH
+        {
+            self.split(rects, depth);
+        }
+    }
+
+    fn split(&mut self, rects: &[Option<Rectangle>], depth: u32)
+    {
+        self.children = Some(Box::new(self.bounds.quadrants().map(Node::new)));
+
+        // the rectangles stay counted here, only where they're kept changes
+        for id in std::mem::take(&mut self.items)
+        {
+            let rect = rects[id.0].expect("inserted rectangles are stored");
+            match self.child_for(&rect)
+            {
+                Some(child) => child.insert(id, rects, depth + 1),
+                None => self.items.push(id),
+            }
+        }
+    }
+
+    // Follows the same path insert took, that's where the id is
+    fn remove(&mut self, id: ItemId, rect: &Rectangle) -> bool
+    {
+        let removed = match self.child_for(rect)
+        {
+            Some(child) => child.remove(id, rect),
+            None => match self.items.iter().position(|&item| item == id)
+            {
+                Some(i) => {
+                    self.items.swap_remove(i);
+                    true
+                }
+                None => false,
+            },
+        };
+
+        if removed
+        {
+            self.len -= 1;
+            if self.len <= NODE_CAPACITY
+            {
+                self.merge();
+            }
+        }
+        removed
+    }
+
+    // Pulls everything below back into this node and drops the quadrants
+    fn merge(&mut self)
+    {
+        let Some(children) = self.children.take() else { return };
+        let mut stack = Vec::from(*children);
+
+        while let Some(node) = stack.pop()
+        {
+            self.items.extend(node.items);
+            stack.extend(node.children.into_iter().flat_map(|children| *children));
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Node>
+    {
+        self.children.iter().flat_map(|children| children.iter())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SpatialIndex
+{
+    root: Node,
+    // indexed by ItemId, None once removed
+    rects: Vec<Option<Rectangle>>,
+    // the None slots in rects, the next insert takes one of these
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl Default for SpatialIndex
+{
+    fn default() -> Self
+    {
+        SpatialIndex::new()
+    }
+}
+
+impl SpatialIndex
+{
+    pub fn new() -> Self
+    {
+        // a square from i32::MIN that reaches past i32::MAX + u32::MAX
+        let min = i64::from(i32::MIN);
+        let side = 1i64 << 33;
+        let bounds = Bounds { left: min, top: min, right: min + side, bottom: min + side };
+
+        SpatialIndex { root: Node::new(bounds), rects: Vec::new(), free: Vec::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, rect: Rectangle) -> ItemId
+    {
+        let id = match self.free.pop()
+        {
+            Some(slot) => {
+                self.rects[slot] = Some(rect);
+                ItemId(slot)
+            }
+            None => {
+                self.rects.push(Some(rect));
+                ItemId(self.rects.len() - 1)
+            }
+        };
+        self.root.insert(id, &self.rects, 0);
+        self.len += 1;
+        id
+    }
+
+    // Gives the rectangle back, None if the id was already removed
+    pub fn remove(&mut self, id: ItemId) -> Option<Rectangle>
+    {
+        let rect = self.rects.get_mut(id.0)?.take()?;
+        let removed = self.root.remove(id, &rect);
+        debug_assert!(removed, "{id:?} wasn't where it was inserted");
+        self.free.push(id.0);
+        self.len -= 1;
+        Some(rect)
+    }
+
+    pub fn get(&self, id: ItemId) -> Option<&Rectangle>
+    {
+        self.rects.get(id.0)?.as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ItemId, &Rectangle)>
+    {
+        self.rects.iter().enumerate().filter_map(|(i, rect)| Some((ItemId(i), rect.as_ref()?)))
+    }
+
+    fn rect(&self, id: ItemId) -> &Rectangle
+    {
+        self.rects[id.0].as_ref().expect("ids in the tree are stored")
+    }
+
+    // Everything sharing some area with `region`, in id order
+    pub fn query(&self, region: &Rectangle) -> Vec<ItemId>
+    {
+        let mut found = Vec::new();
+        let mut stack = vec![&self.root];
+
+        while let Some(node) = stack.pop()
+        {
+            found.extend(node.items.iter().filter(|&&id| self.rect(id).intersects(region)));
+            stack.extend(node.children().filter(|child| child.bounds.overlaps(region)));
+        }
+
+        found.sort_unstable();
+        found
+    }
+
+    // Everything covering the cell at (x, y), in id order
+    pub fn at_point(&self, x: i32, y: i32) -> Vec<ItemId>
+    {
+        let mut found = Vec::new();
+        let mut node = Some(&self.root);
+
+        // only one quadrant can contain the point, no need for a stack
+        while let Some(current) = node
+        {
+            found.extend(current.items.iter().filter(|&&id| self.rect(id).contains_point(x, y)));
+            node = current.children().find(|child| child.bounds.contains_point(i64::from(x), i64::from(y)));
+        }
+
+        found.sort_unstable();
+        found
+    }
+
+    // The rectangle closest to (x, y), measured to its outline, so anything
+    // the point is in is at distance 0. Ties go to the lowest id.
+    pub fn nearest(&self, x: i32, y: i32) -> Option<ItemId>
+    {
+        let (x, y) = (i64::from(x), i64::from(y));
+        let node_distance = |node: &Node| {
+            let b = node.bounds;
+            distance_squared(x, y, b.left, b.top, b.right, b.bottom)
+        };
+
+        let mut best: Option<(u128, ItemId)> = None;
+        // closest nodes first, the heap can't order nodes so they're indexed
+        let mut nodes = vec![&self.root];
+        let mut heap = BinaryHeap::from([Reverse((node_distance(&self.root), 0))]);
+
+        while let Some(Reverse((distance, i))) = heap.pop()
+        {
+            if best.is_some_and(|(best, _)| distance > best)
+            {
+                break;
+            }
+
+            let node = nodes[i];
+            for &id in &node.items
+            {
+                let r = self.rect(id);
+                let candidate = (distance_squared(x, y, r.left(), r.top(), r.right(), r.bottom()), id);
+                if best.is_none_or(|best| candidate < best)
+                {
+                    best = Some(candidate);
+                }
+            }
+            for child in node.children()
+            {
+                heap.push(Reverse((node_distance(child), nodes.len())));
+                nodes.push(child);
+            }
+        }
+
+        best.map(|(_, id)| id)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn node_count(node: &Node) -> usize
+    {
+        1 + node.children().map(node_count).sum::<usize>()
+    }
+
+    #[test]
+    fn storage_stays_bounded_under_churn()
+    {
+        let mut index = SpatialIndex::new();
+        let mut live = Vec::new();
+
+        for round in 0..200
+        {
+            // a crowded patch, so the nodes over it split several levels deep
+            for i in 0..50
+            {
+                let offset = (round * 50 + i) % 400;
+                live.push(index.insert(Rectangle::at(offset, offset, 3, 3)));
+            }
+            for id in live.drain(..)
+            {
+                assert!(index.remove(id).is_some());
+            }
+
+            assert!(index.is_empty());
+            assert!(index.rects.len() <= 50, "round {round}: {} slots", index.rects.len());
+            assert_eq!(node_count(&index.root), 1, "round {round}");
+        }
+
+        // a handful left behind still lives in one leaf
+        let kept: Vec<ItemId> = (0..NODE_CAPACITY as i32).map(|i| index.insert(Rectangle::at(i * 100, 0, 3, 3))).collect();
+        assert!(kept.iter().all(|id| id.index() < 50));
+        assert_eq!(node_count(&index.root), 1);
+    }
+}
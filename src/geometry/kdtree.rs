@@ -0,0 +1,263 @@
+// A k-d tree for "which points are near this one" over any number of
+// dimensions.
+//
+// The tree needs no nodes: the points are reordered so that the middle one
+// of every range splits it, the ones before it being smaller along the
+// range's axis and the ones after it bigger. The axis goes round with the
+// depth, x, y, (z,) x, ...
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use super::{Point, Point3, Scalar};
+
+// Anything with coordinates the tree can split on
+pub trait KdPoint: Copy
+{
+    const DIMENSIONS: usize;
+
+    fn coordinate(&self, axis: usize) -> f64;
+}
+
+impl<T: Scalar> KdPoint for Point<T>
+{
+    const DIMENSIONS: usize = 2;
+
+    fn coordinate(&self, axis: usize) -> f64
+    {
+        if axis == 0 { self.x.to_f64() } else { self.y.to_f64() }
+    }
+}
+
+impl<T: Scalar> KdPoint for Point3<T>
+{
+    const DIMENSIONS: usize = 3;
+
+    fn coordinate(&self, axis: usize) -> f64
+    {
+        match axis
+        {
+            0 => self.x.to_f64(),
+            1 => self.y.to_f64(),
+            _ => self.z.to_f64(),
+        }
+    }
+}
+
+// How far apart two points are. The tree skips the far side of a split
+// when the query is further from the split than what it's looking for, so
+// a metric must never give less than the difference along any one axis.
+pub trait Metric
+{
+    fn distance<P: KdPoint>(&self, a: &P, b: &P) -> f64;
+}
+
+// Straight line, like Point::distance and dist_from_origin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Euclidean;
+
+// Along the axes only, like walking city blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Manhattan;
+
+// The biggest difference along one axis, like a king's moves in chess
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Chebyshev;
+
+fn differences<P: KdPoint>(a: &P, b: &P) -> impl Iterator<Item = f64>
+{
+    (0..P::DIMENSIONS).map(move |axis| (a.coordinate(axis) - b.coordinate(axis)).abs())
+}
+
+impl Metric for Euclidean
+{
+    fn distance<P: KdPoint>(&self, a: &P, b: &P) -> f64
+    {
+        differences(a, b).map(|d| d * d).sum::<f64>().sqrt()
+    }
+}
+
+impl Metric for Manhattan
+{
+    fn distance<P: KdPoint>(&self, a: &P, b: &P) -> f64
+    {
+        differences(a, b).sum()
+    }
+}
+
+impl Metric for Chebyshev
+{
+    fn distance<P: KdPoint>(&self, a: &P, b: &P) -> f64
+    {
+        differences(a, b).fold(0.0, f64::max)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbour<P>
+{
+    // position in the list the tree was built from
+    pub index: usize,
+    pub point: P,
+    pub distance: f64,
+}
+
+// Closer first, of two just as close the one given first. The heap of k
+// nearest keeps the worst one on top.
+#[derive(Debug, Clone, Copy)]
+struct Candidate<P>
+{
+    distance: f64,
+    index: usize,
+    point: P,
+}
+
+impl<P> Candidate<P>
+{
+    fn into_neighbour(self) -> Neighbour<P>
+    {
+        Neighbour { index: self.index, point: self.point, distance: self.distance }
+    }
+}
+
+impl<P> Ord for Candidate<P>
+{
+    fn cmp(&self, other: &Self) -> Ordering
+    {
+        self.distance.total_cmp(&other.distance).then(self.index.cmp(&other.index))
+    }
+}
+
+impl<P> PartialOrd for Candidate<P>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P> PartialEq for Candidate<P>
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<P> Eq for Candidate<P> {}
+
+#[derive(Debug, Clone)]
+pub struct KdTree<P>
+{
+    // (index in the original list, point), in tree order
+    points: Vec<(usize, P)>,
+}
+
+impl<P: KdPoint> KdTree<P>
+{
+    pub fn build(points: &[P]) -> Self
+    {
+        let mut points: Vec<(usize, P)> = points.iter().copied().enumerate().collect();
+        split(&mut points, 0);
+        KdTree { points }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.points.is_empty()
+    }
+
+    pub fn nearest<M: Metric>(&self, query: &P, metric: &M) -> Option<Neighbour<P>>
+    {
+        self.k_nearest(query, 1, metric).pop()
+    }
+
+    // The k points closest to `query`, closest first
+    pub fn k_nearest<M: Metric>(&self, query: &P, k: usize, metric: &M) -> Vec<Neighbour<P>>
+    {
+        if k == 0
+        {
+            return Vec::new();
+        }
+
+        let mut best: BinaryHeap<Candidate<P>> = BinaryHeap::with_capacity(k + 1);
+        self.visit(&self.points, 0, query, metric, &mut |candidate| {
+            best.push(candidate);
+            if best.len() > k
+            {
+                best.pop();
+            }
+            // how far the far sides still have to be looked at
+            if best.len() == k { best.peek().map_or(f64::INFINITY, |worst| worst.distance) } else { f64::INFINITY }
+        });
+
+        best.into_sorted_vec().into_iter().map(Candidate::into_neighbour).collect()
+    }
+
+    // Every point at most `radius` away from `query`, closest first
+    pub fn within_radius<M: Metric>(&self, query: &P, radius: f64, metric: &M) -> Vec<Neighbour<P>>
+    {
+        let mut found = Vec::new();
+        self.visit(&self.points, 0, query, metric, &mut |candidate| {
+            if candidate.distance <= radius
+            {
+                found.push(candidate);
+            }
+            radius
+        });
+
+        found.sort_unstable();
+        found.into_iter().map(Candidate::into_neighbour).collect()
+    }
+
+    // Offers the points of `range` to `found`, near side of every split
+    // first. `found` answers how far from a split the far side is still
+    // worth looking at.
+    fn visit<M: Metric>(&self, range: &[(usize, P)], depth: usize, query: &P, metric: &M, found: &mut dyn FnMut(Candidate<P>) -> f64) -> f64
+    {
+        let Some(&(index, point)) = range.get(range.len() / 2) else {
+            return f64::INFINITY;
+        };
+        let axis = depth % P::DIMENSIONS;
+        let (before, after) = (&range[..range.len() / 2], &range[range.len() / 2 + 1..]);
+
+        let mut reach = found(Candidate { distance: metric.distance(query, &point), index, point });
+
+        let offset = query.coordinate(axis) - point.coordinate(axis);
+        let (near, far) = if offset < 0.0 { (before, after) } else { (after, before) };
+
+        if !near.is_empty()
+        {
+            reach = self.visit(near, depth + 1, query, metric, found);
+        }
+        // <= so points just as far as the worst one are still compared
+        if !far.is_empty() && offset.abs() <= reach
+        {
+            reach = self.visit(far, depth + 1, query, metric, found);
+        }
+        reach
+    }
+}
+
+// Puts the median along the axis in the middle, smaller before it, bigger
+// after it, then does the same for both halves on the next axis
+fn split<P: KdPoint>(points: &mut [(usize, P)], depth: usize)
+{
+    if points.len() <= 1
+    {
+        return;
+    }
+
+    let axis = depth % P::DIMENSIONS;
+    let middle = points.len() / 2;
+    points.select_nth_unstable_by(middle, |(_, a), (_, b)| a.coordinate(axis).total_cmp(&b.coordinate(axis)));
+
+    let (before, rest) = points.split_at_mut(middle);
+    split(before, depth + 1);
+    split(&mut rest[1..], depth + 1);
+}
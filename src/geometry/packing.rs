@@ -0,0 +1,359 @@
+// Packs rectangles into as few fixed size bins as possible, e.g. sprites
+// into texture atlases.
+//
+// Three heuristics, from simplest to tightest:
+// - Shelf: rows of rectangles, a new row starts when one is full
+// - Guillotine: the free space is a list of rectangles, every placement
+//   cuts the one it went into in two
+// - MaxRects: the free space is every maximal free rectangle, they overlap,
+//   so a placement can use space no guillotine cut would have left
+//
+// Bins are filled first fit: a rectangle goes into the first open bin it
+// fits in and a new bin is only opened when it fits in none of them.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use super::Rectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Heuristic
+{
+    Shelf,
+    Guillotine,
+    #[default]
+    MaxRects,
+}
+
+impl Heuristic
+{
+    pub const ALL: [Heuristic; 3] = [Heuristic::Shelf, Heuristic::Guillotine, Heuristic::MaxRects];
+
+    pub fn name(self) -> &'static str
+    {
+        match self
+        {
+            Heuristic::Shelf => "shelf",
+            Heuristic::Guillotine => "guillotine",
+            Heuristic::MaxRects => "maxrects",
+        }
+    }
+
+    fn new_bin(self, width: u32, height: u32) -> Box<dyn Bin>
+    {
+        let space = Rectangle::new(width, height);
+        match self
+        {
+            Heuristic::Shelf => Box::new(ShelfBin { space, shelves: Vec::new() }),
+            Heuristic::Guillotine => Box::new(GuillotineBin { free: vec![space] }),
+            Heuristic::MaxRects => Box::new(MaxRectsBin { free: vec![space] }),
+        }
+    }
+}
+
+impl fmt::Display for Heuristic
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Heuristic
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Heuristic::ALL
+            .into_iter()
+            .find(|h| h.name() == s)
+            .ok_or_else(|| format!("unknown packing heuristic '{s}', expected shelf, guillotine or maxrects"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackConfig
+{
+    pub heuristic: Heuristic,
+    pub bin_width: u32,
+    pub bin_height: u32,
+    // rectangles may be turned by 90° if they fit better that way
+    pub allow_rotation: bool,
+}
+
+impl Default for PackConfig
+{
+    fn default() -> Self
+    {
+        PackConfig { heuristic: Heuristic::default(), bin_width: 1024, bin_height: 1024, allow_rotation: false }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement
+{
+    // position in the list given to pack
+    pub index: usize,
+    pub bin: usize,
+    // where it went in the bin, with width and height swapped when rotated
+    pub rect: Rectangle,
+    pub rotated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Packing
+{
+    pub config: PackConfig,
+    pub bins: usize,
+    // in the order the rectangles were placed
+    pub placements: Vec<Placement>,
+    // indexes of the rectangles bigger than a bin
+    pub unplaced: Vec<usize>,
+}
+
+impl Packing
+{
+    pub fn placements_in(&self, bin: usize) -> impl Iterator<Item = &Placement>
+    {
+        self.placements.iter().filter(move |p| p.bin == bin)
+    }
+
+    fn bin_area(&self) -> u64
+    {
+        Rectangle::new(self.config.bin_width, self.config.bin_height).area()
+    }
+
+    // Share of the bin covered by rectangles, 0.0 to 1.0
+    pub fn bin_utilisation(&self, bin: usize) -> f64
+    {
+        let used: u64 = self.placements_in(bin).map(|p| p.rect.area()).sum();
+        used as f64 / self.bin_area().max(1) as f64
+    }
+
+    // Share of all the bins covered by rectangles, 0.0 to 1.0
+    pub fn utilisation(&self) -> f64
+    {
+        let used: u64 = self.placements.iter().map(|p| p.rect.area()).sum();
+        used as f64 / (self.bin_area() * self.bins as u64).max(1) as f64
+    }
+}
+
+// Free space of one bin
+trait Bin
+{
+    // Where a width x height rectangle goes, turned if that's the bool
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rectangle, bool)>;
+}
+
+// The ways a rectangle can be put down, upright first
+fn orientations(width: u32, height: u32, allow_rotation: bool) -> impl Iterator<Item = (u32, u32, bool)>
+{
+    let rotated = (allow_rotation && width != height).then_some((height, width, true));
+    [Some((width, height, false)), rotated].into_iter().flatten()
+}
+
+// A width x height rectangle in the top left corner of `space`
+fn corner(space: &Rectangle, width: u32, height: u32) -> Rectangle
+{
+    Rectangle { width, height, ..*space }
+}
+
+struct Shelf
+{
+    y: i64,
+    height: u32,
+    used: u32,
+}
+
+struct ShelfBin
+{
+    space: Rectangle,
+    shelves: Vec<Shelf>,
+}
+
+impl Bin for ShelfBin
+{
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rectangle, bool)>
+    {
+        // the open shelf wasting the least height
+        let best = self
+            .shelves
+            .iter()
+            .enumerate()
+            .flat_map(|(i, shelf)| orientations(width, height, allow_rotation).map(move |o| (i, shelf, o)))
+            .filter(|(_, shelf, (w, h, _))| *h <= shelf.height && u64::from(shelf.used) + u64::from(*w) <= u64::from(self.space.width))
+            .min_by_key(|(_, shelf, (_, h, _))| shelf.height - h)
+            .map(|(i, _, o)| (i, o));
+
+        let (i, (w, h, rotated)) = match best
+        {
+            Some(found) => found,
+            None => {
+                // a new shelf, as low as possible so the next ones fit too
+                let y = self.shelves.last().map_or(self.space.top(), |s| s.y + i64::from(s.height));
+                let o = orientations(width, height, allow_rotation)
+                    .filter(|(w, h, _)| *w <= self.space.width && y + i64::from(*h) <= self.space.bottom())
+                    .min_by_key(|(_, h, _)| *h)?;
+                self.shelves.push(Shelf { y, height: o.1, used: 0 });
+                (self.shelves.len() - 1, o)
+            }
+        };
+
+        let shelf = &mut self.shelves[i];
+        let x = self.space.left() + i64::from(shelf.used);
+        shelf.used += w;
+        Some((Rectangle::from_edges(x, shelf.y, x + i64::from(w), shelf.y + i64::from(h))?, rotated))
+    }
+}
+
+struct GuillotineBin
+{
+    free: Vec<Rectangle>,
+}
+
+impl Bin for GuillotineBin
+{
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rectangle, bool)>
+    {
+        // best area fit: the free rectangle with the least left over
+        let (i, (w, h, rotated)) = self
+            .free
+            .iter()
+            .enumerate()
+            .flat_map(|(i, free)| orientations(width, height, allow_rotation).map(move |o| (i, free, o)))
+            .filter(|(_, free, (w, h, _))| *w <= free.width && *h <= free.height)
+            .min_by_key(|(_, free, (w, h, _))| free.area() - Rectangle::new(*w, *h).area())
+            .map(|(i, _, o)| (i, o))?;
+
+        let free = self.free.swap_remove(i);
+        let placed = corner(&free, w, h);
+
+        // cut along the shorter leftover side, so the bigger piece stays big
+        let (right_height, bottom_width) = if free.width - w < free.height - h { (h, free.width) } else { (free.height, w) };
+        let right = Rectangle::from_edges(placed.right(), free.top(), free.right(), free.top() + i64::from(right_height));
+        let bottom = Rectangle::from_edges(free.left(), placed.bottom(), free.left() + i64::from(bottom_width), free.bottom());
+        self.free.extend([right, bottom].into_iter().flatten().filter(|r| !r.is_empty()));
+
+        Some((placed, rotated))
+    }
+}
+
+struct MaxRectsBin
+{
+    free: Vec<Rectangle>,
+}
+
+impl MaxRectsBin
+{
+    // What's left of `free` around `used`, up to four overlapping pieces
+    fn split(free: &Rectangle, used: &Rectangle) -> Vec<Rectangle>
+    {
+        let pieces = [
+            Rectangle::from_edges(free.left(), free.top(), used.left(), free.bottom()),
+            Rectangle::from_edges(used.right(), free.top(), free.right(), free.bottom()),
+            Rectangle::from_edges(free.left(), free.top(), free.right(), used.top()),
+            Rectangle::from_edges(free.left(), used.bottom(), free.right(), free.bottom()),
+        ];
+        pieces.into_iter().flatten().filter(|r| !r.is_empty()).collect()
+    }
+}
+
+impl Bin for MaxRectsBin
+{
+    fn insert(&mut self, width: u32, height: u32, allow_rotation: bool) -> Option<(Rectangle, bool)>
+    {
+        // best short side fit: the smallest leftover along either side
+        let (placed, rotated) = self
+            .free
+            .iter()
+            .flat_map(|free| orientations(width, height, allow_rotation).map(move |o| (free, o)))
+            .filter(|(free, (w, h, _))| *w <= free.width && *h <= free.height)
+            .min_by_key(|(free, (w, h, _))| {
+                let (dw, dh) = (free.width - w, free.height - h);
+                (dw.min(dh), dw.max(dh))
+            })
+            .map(|(free, (w, h, rotated))| (corner(free, w, h), rotated))?;
+
+        let mut free = Vec::with_capacity(self.free.len() + 4);
+        for r in &self.free
+        {
+            if r.intersects(&placed)
+            {
+                free.extend(MaxRectsBin::split(r, &placed));
+            }
+            else
+            {
+                free.push(*r);
+            }
+        }
+
+        // pieces inside other pieces are never the best fit, drop them
+        // (of two equal ones the first stays)
+        let keep: Vec<bool> = (0..free.len())
+            .map(|i| !free.iter().enumerate().any(|(j, other)| j != i && other.contains_rect(&free[i]) && (free[i] != *other || j < i)))
+            .collect();
+        self.free = free.into_iter().zip(keep).filter_map(|(r, keep)| keep.then_some(r)).collect();
+
+        Some((placed, rotated))
+    }
+}
+
+// Packs the rectangles, only their sizes are used. They go in biggest first,
+// which packs a lot tighter than the order they were given in.
+pub fn pack(rects: &[Rectangle], config: &PackConfig) -> Packing
+{
+    let fits_empty_bin = |r: &Rectangle| {
+        orientations(r.width, r.height, config.allow_rotation).any(|(w, h, _)| w <= config.bin_width && h <= config.bin_height)
+    };
+
+    let mut order: Vec<usize> = (0..rects.len()).collect();
+    // shelves are decided by height, lying down if it can be turned, the
+    // others by the longer side
+    match config.heuristic
+    {
+        Heuristic::Shelf if config.allow_rotation => {
+            order.sort_by_key(|&i| Reverse((rects[i].width.min(rects[i].height), rects[i].width.max(rects[i].height))))
+        }
+        Heuristic::Shelf => order.sort_by_key(|&i| Reverse((rects[i].height, rects[i].width))),
+        _ => order.sort_by_key(|&i| Reverse((rects[i].width.max(rects[i].height), rects[i].area()))),
+    }
+
+    let mut bins: Vec<Box<dyn Bin>> = Vec::new();
+    let mut placements = Vec::with_capacity(rects.len());
+    let mut unplaced = Vec::new();
+
+    for index in order
+    {
+        let r = &rects[index];
+        if !fits_empty_bin(r)
+        {
+            unplaced.push(index);
+            continue;
+        }
+
+        let mut placed = bins.iter_mut().enumerate().find_map(|(bin, free)| {
+            free.insert(r.width, r.height, config.allow_rotation).map(|(rect, rotated)| (bin, rect, rotated))
+        });
+        if placed.is_none()
+        {
+            let mut free = config.heuristic.new_bin(config.bin_width, config.bin_height);
+            placed = free.insert(r.width, r.height, config.allow_rotation).map(|(rect, rotated)| (bins.len(), rect, rotated));
+            if placed.is_some()
+            {
+                bins.push(free);
+            }
+        }
+
+        match placed
+        {
+            Some((bin, rect, rotated)) => placements.push(Placement { index, bin, rect, rotated }),
+            // bins wider than i32::MAX can't hold rectangles at their far end
+            None => unplaced.push(index),
+        }
+    }
+
+    unplaced.sort_unstable();
+    Packing { config: *config, bins: bins.len(), placements, unplaced }
+}
@@ -0,0 +1,310 @@
+// Points and vectors over any of the number types geometry uses, the grown
+// up version of chapter 10's Point<T, U>. The same type is used for both, a
+// point is the vector from the origin to it.
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+use super::predicates::{orientation_f64, orientation_i128, Orientation};
+
+// The number types coordinates can be. Integers follow their own rules: a
+// u32 result below zero panics in debug builds like any u32 subtraction.
+pub trait Scalar:
+    Copy + Debug + Default + PartialEq + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn to_f64(self) -> f64;
+
+    // None if `value` is out of range, NaN or infinite. Integers get the
+    // nearest whole number.
+    fn from_f64(value: f64) -> Option<Self>;
+
+    // Integers exactly, None for floats
+    fn to_i128(self) -> Option<i128>;
+
+    // None if `value` is out of range
+    fn from_i128(value: i128) -> Option<Self>;
+
+    // The same number in another Scalar, None if it doesn't fit. Between
+    // integers it's exact, f64 is only used when a float is involved: it
+    // can't hold every i64.
+    fn cast<U: Scalar>(self) -> Option<U>
+    {
+        match self.to_i128()
+        {
+            Some(value) => U::from_i128(value),
+            None => U::from_f64(self.to_f64()),
+        }
+    }
+
+    // The bigger of the two, for floats the one that isn't NaN
+    fn max_of(self, other: Self) -> Self;
+
+    fn min_of(self, other: Self) -> Self;
+
+    // Which way a -> b -> c turns, exactly, see predicates.rs
+    fn orientation(a: Point<Self>, b: Point<Self>, c: Point<Self>) -> Orientation;
+}
+
+macro_rules! integer_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t
+        {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn to_f64(self) -> f64
+            {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Option<Self>
+            {
+                let value = value.round();
+                // not `<= MAX as f64`, i64::MAX as f64 rounds up to 2^63 which doesn't fit
+                (value >= <$t>::MIN as f64 && value < <$t>::MAX as f64 + 1.0).then(|| value as $t)
+            }
+
+            fn to_i128(self) -> Option<i128>
+            {
+                Some(i128::from(self))
+            }
+
+            fn from_i128(value: i128) -> Option<Self>
+            {
+                <$t>::try_from(value).ok()
+            }
+
+            fn max_of(self, other: Self) -> Self
+            {
+                Ord::max(self, other)
+            }
+
+            fn min_of(self, other: Self) -> Self
+            {
+                Ord::min(self, other)
+            }
+
+            fn orientation(a: Point<Self>, b: Point<Self>, c: Point<Self>) -> Orientation
+            {
+                let wide = |p: Point<Self>| (i128::from(p.x), i128::from(p.y));
+                orientation_i128(wide(a), wide(b), wide(c))
+            }
+        }
+    )*};
+}
+
+macro_rules! float_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t
+        {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+
+            fn to_f64(self) -> f64
+            {
+                f64::from(self)
+            }
+
+            fn from_f64(value: f64) -> Option<Self>
+            {
+                let converted = value as $t;
+                converted.is_finite().then_some(converted)
+            }
+
+            fn to_i128(self) -> Option<i128>
+            {
+                None
+            }
+
+            fn from_i128(value: i128) -> Option<Self>
+            {
+                Some(value as $t)
+            }
+
+            fn max_of(self, other: Self) -> Self
+            {
+                self.max(other)
+            }
+
+            fn min_of(self, other: Self) -> Self
+            {
+                self.min(other)
+            }
+
+            fn orientation(a: Point<Self>, b: Point<Self>, c: Point<Self>) -> Orientation
+            {
+                let wide = |p: Point<Self>| (f64::from(p.x), f64::from(p.y));
+                orientation_f64(wide(a), wide(b), wide(c))
+            }
+        }
+    )*};
+}
+
+integer_scalar!(u32, i32, i64);
+float_scalar!(f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point<T>
+{
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Scalar> Point<T>
+{
+    pub fn new(x: T, y: T) -> Self
+    {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Self
+    {
+        Point { x: T::ZERO, y: T::ZERO }
+    }
+
+    pub fn x(&self) -> &T
+    {
+        &self.x
+    }
+
+    pub fn y(&self) -> &T
+    {
+        &self.y
+    }
+
+    // Same point in another number type, None if it doesn't fit
+    pub fn cast<U: Scalar>(self) -> Option<Point<U>>
+    {
+        Some(Point { x: self.x.cast()?, y: self.y.cast()? })
+    }
+
+    // In f64 like the distances, T can be too small (i32) or unsigned
+    // (u32) for the products
+    pub fn dot(self, other: Point<T>) -> f64
+    {
+        self.x.to_f64() * other.x.to_f64() + self.y.to_f64() * other.y.to_f64()
+    }
+
+    // The z of the 3D cross product: positive when `other` is counter
+    // clockwise from self (with y going up), 0 when they're parallel. For
+    // the exact sign use orientation().
+    pub fn cross(self, other: Point<T>) -> f64
+    {
+        self.x.to_f64() * other.y.to_f64() - self.y.to_f64() * other.x.to_f64()
+    }
+
+    // Distances are f64 for every type, they're rarely whole numbers. The
+    // difference is taken in f64 too, so u32 points can't underflow.
+    pub fn distance_squared(self, other: Point<T>) -> f64
+    {
+        let dx = self.x.to_f64() - other.x.to_f64();
+        let dy = self.y.to_f64() - other.y.to_f64();
+        dx * dx + dy * dy
+    }
+
+    pub fn distance(self, other: Point<T>) -> f64
+    {
+        self.distance_squared(other).sqrt()
+    }
+
+    pub fn dist_from_origin(self) -> f64
+    {
+        self.distance(Point::origin())
+    }
+}
+
+impl<T: Scalar> Add for Point<T>
+{
+    type Output = Point<T>;
+
+    fn add(self, other: Point<T>) -> Point<T>
+    {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl<T: Scalar> Sub for Point<T>
+{
+    type Output = Point<T>;
+
+    fn sub(self, other: Point<T>) -> Point<T>
+    {
+        Point { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+// Scaling, both coordinates times the same number
+impl<T: Scalar> Mul<T> for Point<T>
+{
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T>
+    {
+        Point { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+impl<T: Scalar> From<(T, T)> for Point<T>
+{
+    fn from((x, y): (T, T)) -> Self
+    {
+        Point { x, y }
+    }
+}
+
+// A point in space, for what works in any number of dimensions (see
+// kdtree.rs). Only the basics, the 2D maths is on Point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point3<T>
+{
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Scalar> Point3<T>
+{
+    pub fn new(x: T, y: T, z: T) -> Self
+    {
+        Point3 { x, y, z }
+    }
+
+    pub fn distance(self, other: Point3<T>) -> f64
+    {
+        let d = |a: T, b: T| a.to_f64() - b.to_f64();
+        let (dx, dy, dz) = (d(self.x, other.x), d(self.y, other.y), d(self.z, other.z));
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+}
+
+impl<T: Scalar> Add for Point3<T>
+{
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T>
+    {
+        Point3 { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+    }
+}
+
+impl<T: Scalar> Sub for Point3<T>
+{
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T>
+    {
+        Point3 { x: self.x - other.x, y: self.y - other.y, z: self.z - other.z }
+    }
+}
+
+// Left to right, then bottom to top. NaN compares equal to everything, it
+// has no place in the order.
+pub(crate) fn by_x_then_y<T: Scalar>(p: &Point<T>, q: &Point<T>) -> Ordering
+{
+    let x = p.x.partial_cmp(&q.x).unwrap_or(Ordering::Equal);
+    x.then(p.y.partial_cmp(&q.y).unwrap_or(Ordering::Equal))
+}
@@ -0,0 +1,194 @@
+// Polygons and line segments. Every yes or no question here is answered by
+// orientation(), so it's exact for floats too; the numbers (areas,
+// crossing points) are f64.
+
+use super::predicates::{orientation, Orientation};
+use super::point::by_x_then_y;
+use super::{Point, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Containment
+{
+    Inside,
+    Outside,
+    OnBoundary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Segment<T>
+{
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SegmentIntersection<T>
+{
+    // where they cross, or touch
+    Point(Point<f64>),
+    // collinear segments sharing a piece, the piece is made of their ends
+    Overlap(Segment<T>),
+}
+
+// Is c between a and b, given that the three are collinear
+fn within<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> bool
+{
+    a.x.min_of(b.x) <= c.x && c.x <= a.x.max_of(b.x) && a.y.min_of(b.y) <= c.y && c.y <= a.y.max_of(b.y)
+}
+
+fn to_f64<T: Scalar>(p: Point<T>) -> Point<f64>
+{
+    Point::new(p.x.to_f64(), p.y.to_f64())
+}
+
+impl<T: Scalar> Segment<T>
+{
+    pub fn new(start: Point<T>, end: Point<T>) -> Self
+    {
+        Segment { start, end }
+    }
+
+    pub fn length(&self) -> f64
+    {
+        self.start.distance(self.end)
+    }
+
+    pub fn contains_point(&self, p: Point<T>) -> bool
+    {
+        orientation(self.start, self.end, p) == Orientation::Collinear && within(self.start, self.end, p)
+    }
+
+    // True if they share at least one point, touching ends included
+    pub fn intersects(&self, other: &Segment<T>) -> bool
+    {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+        let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+
+        let crossing = o1 != o2 && o3 != o4 && ![o1, o2, o3, o4].contains(&Orientation::Collinear);
+        crossing || self.contains_point(c) || self.contains_point(d) || other.contains_point(a) || other.contains_point(b)
+    }
+
+    pub fn intersection(&self, other: &Segment<T>) -> Option<SegmentIntersection<T>>
+    {
+        if !self.intersects(other)
+        {
+            return None;
+        }
+
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let parallel = orientation(a, b, c) == Orientation::Collinear && orientation(a, b, d) == Orientation::Collinear;
+        if !parallel
+        {
+            // a + t (b - a), with t from the cross products, in f64 as the
+            // crossing is rarely on whole numbers
+            let (a, b, c, d) = (to_f64(a), to_f64(b), to_f64(c), to_f64(d));
+            let t = (c - a).cross(d - c) / (b - a).cross(d - c);
+            let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+            return Some(SegmentIntersection::Point(a + (b - a) * t));
+        }
+
+        // collinear: the overlap runs between the two middle ends. A
+        // segment that's a single point is collinear with everything, so
+        // the ends are checked against both segments.
+        let mut shared: Vec<Point<T>> = [a, b, c, d].into_iter().filter(|&p| self.contains_point(p) && other.contains_point(p)).collect();
+        shared.sort_by(by_x_then_y);
+        shared.dedup();
+
+        match shared.as_slice()
+        {
+            [] => None,
+            [p] => Some(SegmentIntersection::Point(to_f64(*p))),
+            [first, .., last] => Some(SegmentIntersection::Overlap(Segment::new(*first, *last))),
+        }
+    }
+}
+
+// A closed polygon, the last vertex connects back to the first one
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Polygon<T>
+{
+    pub vertices: Vec<Point<T>>,
+}
+
+impl<T: Scalar> Polygon<T>
+{
+    pub fn new(vertices: Vec<Point<T>>) -> Self
+    {
+        Polygon { vertices }
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment<T>> + '_
+    {
+        let n = self.vertices.len();
+        (0..n).map(move |i| Segment::new(self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    // Shoelace formula, positive when the vertices go counter clockwise
+    // (with y going up)
+    pub fn signed_area(&self) -> f64
+    {
+        self.edges().map(|e| to_f64(e.start).cross(to_f64(e.end))).sum::<f64>() / 2.0
+    }
+
+    pub fn area(&self) -> f64
+    {
+        self.signed_area().abs()
+    }
+
+    pub fn perimeter(&self) -> f64
+    {
+        self.edges().map(|e| e.length()).sum()
+    }
+
+    // The center of mass, None for polygons without area
+    pub fn centroid(&self) -> Option<Point<f64>>
+    {
+        let area = self.signed_area();
+        if area == 0.0 || !area.is_finite()
+        {
+            return None;
+        }
+
+        let (mut x, mut y) = (0.0, 0.0);
+        for e in self.edges()
+        {
+            let (p, q) = (to_f64(e.start), to_f64(e.end));
+            let cross = p.cross(q);
+            x += (p.x + q.x) * cross;
+            y += (p.y + q.y) * cross;
+        }
+        Some(Point::new(x / (6.0 * area), y / (6.0 * area)))
+    }
+
+    // Winding number test, so it works for polygons crossing themselves and
+    // for either direction. Points on an edge are neither in nor out.
+    pub fn contains(&self, p: Point<T>) -> Containment
+    {
+        let mut winding = 0;
+
+        for edge in self.edges()
+        {
+            let (a, b) = (edge.start, edge.end);
+            if edge.contains_point(p)
+            {
+                return Containment::OnBoundary;
+            }
+
+            if a.y <= p.y
+            {
+                // going up past p, with p on the left
+                if b.y > p.y && orientation(a, b, p) == Orientation::CounterClockwise
+                {
+                    winding += 1;
+                }
+            }
+            else if b.y <= p.y && orientation(a, b, p) == Orientation::Clockwise
+            {
+                winding -= 1;
+            }
+        }
+
+        if winding == 0 { Containment::Outside } else { Containment::Inside }
+    }
+}
@@ -0,0 +1,129 @@
+// Which side of a line a point is on, answered exactly. Everything in
+// polygon.rs and hull.rs is decided by this one question, and getting it
+// wrong for nearly collinear points makes hulls skip points or cross
+// themselves.
+//
+// Integers are easy, the products just need enough bits. Floats round, so
+// the determinant is first computed the fast way together with how big its
+// rounding error can be (Shewchuk's orient2d filter). Only if the error
+// could flip the sign is it computed again, exactly, as a sum of floats
+// that don't overlap.
+
+use std::cmp::Ordering;
+
+use super::{Point, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation
+{
+    // c is to the left of a -> b (with y going up)
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+impl Orientation
+{
+    fn from_sign(sign: Ordering) -> Self
+    {
+        match sign
+        {
+            Ordering::Greater => Orientation::CounterClockwise,
+            Ordering::Less => Orientation::Clockwise,
+            Ordering::Equal => Orientation::Collinear,
+        }
+    }
+}
+
+// Which way a -> b -> c turns
+pub fn orientation<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> Orientation
+{
+    T::orientation(a, b, c)
+}
+
+// How a * b compares to c * d, without overflowing. The differences of
+// two i64s need 65 bits, their products up to 128 without the sign.
+fn compare_products(a: i128, b: i128, c: i128, d: i128) -> Ordering
+{
+    let sign = |x: i128, y: i128| (x.signum() * y.signum()).cmp(&0);
+    let (left_sign, right_sign) = (sign(a, b), sign(c, d));
+    if left_sign != right_sign
+    {
+        return left_sign.cmp(&right_sign);
+    }
+
+    let left = a.unsigned_abs() * b.unsigned_abs();
+    let right = c.unsigned_abs() * d.unsigned_abs();
+    match left_sign
+    {
+        Ordering::Less => right.cmp(&left),
+        _ => left.cmp(&right),
+    }
+}
+
+pub(crate) fn orientation_i128(a: (i128, i128), b: (i128, i128), c: (i128, i128)) -> Orientation
+{
+    // (b - a) x (c - a) compared to 0
+    Orientation::from_sign(compare_products(b.0 - a.0, c.1 - a.1, b.1 - a.1, c.0 - a.0))
+}
+
+// a + b as the rounded sum and what rounding lost, exactly
+fn two_sum(a: f64, b: f64) -> (f64, f64)
+{
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+// a * b as the rounded product and what rounding lost, exactly (mul_add
+// rounds only once)
+fn two_product(a: f64, b: f64) -> (f64, f64)
+{
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+// Adds b to the expansion, a list of non overlapping floats from the
+// smallest to the biggest whose exact sum is the value
+fn grow_expansion(expansion: &mut Vec<f64>, b: f64)
+{
+    let mut q = b;
+    for component in expansion.iter_mut()
+    {
+        let (sum, error) = two_sum(q, *component);
+        *component = error;
+        q = sum;
+    }
+    expansion.push(q);
+}
+
+pub(crate) fn orientation_f64(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> Orientation
+{
+    let left = (a.0 - c.0) * (b.1 - c.1);
+    let right = (a.1 - c.1) * (b.0 - c.0);
+    let det = left - right;
+
+    // Shewchuk's bound on the error of det computed like that
+    const EPSILON: f64 = f64::EPSILON / 2.0;
+    let error_bound = (3.0 + 16.0 * EPSILON) * EPSILON * (left.abs() + right.abs());
+    if det.abs() > error_bound || det.is_nan()
+    {
+        return Orientation::from_sign(det.partial_cmp(&0.0).unwrap_or(Ordering::Equal));
+    }
+
+    // a.x b.y - a.x c.y - a.y b.x + a.y c.x + b.x c.y - b.y c.x, every
+    // product split in two floats and added up without rounding
+    let terms = [(a.0, b.1), (-a.0, c.1), (-a.1, b.0), (a.1, c.0), (b.0, c.1), (-b.1, c.0)];
+    let mut expansion = Vec::with_capacity(12);
+    for (x, y) in terms
+    {
+        let (product, error) = two_product(x, y);
+        grow_expansion(&mut expansion, error);
+        grow_expansion(&mut expansion, product);
+    }
+
+    // the biggest component that isn't zero has the sign of the sum
+    let most_significant = expansion.iter().rev().find(|&&c| c != 0.0).copied().unwrap_or(0.0);
+    Orientation::from_sign(most_significant.partial_cmp(&0.0).unwrap_or(Ordering::Equal))
+}
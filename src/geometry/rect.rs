@@ -0,0 +1,131 @@
+// Rectangles whose position and sides have the same type, for the maths
+// Rectangle's whole cells get in the way of: float coordinates, centers,
+// scaling. Their methods are on Rect<T> only, the ones with the same names
+// on plain Rectangle work in i64 cells instead.
+
+use super::{Point, Rectangle, Scalar};
+
+pub type Rect<T> = Rectangle<T, T>;
+
+impl<P: Scalar, S: Scalar> Rectangle<P, S>
+{
+    // the corner with the smallest coordinates
+    pub fn origin(&self) -> Point<P>
+    {
+        Point::new(self.x, self.y)
+    }
+
+    pub fn size(&self) -> Point<S>
+    {
+        Point::new(self.width, self.height)
+    }
+
+    pub fn center(&self) -> Point<f64>
+    {
+        let half = |origin: P, size: S| origin.to_f64() + size.to_f64() / 2.0;
+        Point::new(half(self.x, self.width), half(self.y, self.height))
+    }
+}
+
+impl<T: Scalar> Rect<T>
+{
+    // Not `new`, that's the book's Rectangle::new(width, height)
+    pub fn from_origin_size(origin: Point<T>, size: Point<T>) -> Self
+    {
+        Rectangle { x: origin.x, y: origin.y, width: size.x, height: size.y }
+    }
+
+    // The rectangle spanning two opposite corners, in any order
+    pub fn from_corners(a: Point<T>, b: Point<T>) -> Self
+    {
+        let origin = Point::new(a.x.min_of(b.x), a.y.min_of(b.y));
+        let end = Point::new(a.x.max_of(b.x), a.y.max_of(b.y));
+        Rect::from_origin_size(origin, end - origin)
+    }
+
+    // the corner opposite to origin
+    pub fn end(&self) -> Point<T>
+    {
+        self.origin() + self.size()
+    }
+
+    pub fn area(&self) -> T
+    {
+        self.width * self.height
+    }
+
+    pub fn translate(&self, by: Point<T>) -> Self
+    {
+        Rect::from_origin_size(self.origin() + by, self.size())
+    }
+
+    // Grows (or shrinks) away from the origin corner
+    pub fn scale(&self, factor: T) -> Self
+    {
+        Rect::from_origin_size(self.origin(), self.size() * factor)
+    }
+
+    // Borders included on the origin side, excluded on the other, so
+    // rectangles sharing an edge don't both contain the points on it
+    pub fn contains_point(&self, p: Point<T>) -> bool
+    {
+        let end = self.end();
+        self.x <= p.x && p.x < end.x && self.y <= p.y && p.y < end.y
+    }
+
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Rect<T>>
+    {
+        let (end, other_end) = (self.end(), other.end());
+        let origin = Point::new(self.x.max_of(other.x), self.y.max_of(other.y));
+        let corner = Point::new(end.x.min_of(other_end.x), end.y.min_of(other_end.y));
+
+        (origin.x < corner.x && origin.y < corner.y).then(|| Rect::from_origin_size(origin, corner - origin))
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool
+    {
+        self.intersection(other).is_some()
+    }
+
+    pub fn union_bounds(&self, other: &Rect<T>) -> Rect<T>
+    {
+        let (end, other_end) = (self.end(), other.end());
+        let origin = Point::new(self.x.min_of(other.x), self.y.min_of(other.y));
+        let corner = Point::new(end.x.max_of(other_end.x), end.y.max_of(other_end.y));
+        Rect::from_origin_size(origin, corner - origin)
+    }
+
+    // Same rectangle in another number type, None if it doesn't fit
+    pub fn cast<U: Scalar>(&self) -> Option<Rect<U>>
+    {
+        Some(Rect::from_origin_size(self.origin().cast()?, self.size().cast()?))
+    }
+}
+
+// i64 holds every Rectangle, no matter where it is or how big
+impl From<Rectangle> for Rect<i64>
+{
+    fn from(r: Rectangle) -> Self
+    {
+        Rectangle { x: r.left(), y: r.top(), width: i64::from(r.width), height: i64::from(r.height) }
+    }
+}
+
+impl From<Rectangle> for Rect<f64>
+{
+    fn from(r: Rectangle) -> Self
+    {
+        Rectangle { x: f64::from(r.x), y: f64::from(r.y), width: f64::from(r.width), height: f64::from(r.height) }
+    }
+}
+
+impl TryFrom<Rect<i64>> for Rectangle
+{
+    type Error = String;
+
+    fn try_from(r: Rect<i64>) -> Result<Self, Self::Error>
+    {
+        let end = r.end();
+        Rectangle::from_edges(r.x, r.y, end.x, end.y).ok_or_else(|| format!("{r:?} doesn't fit in a Rectangle"))
+    }
+}
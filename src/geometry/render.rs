@@ -0,0 +1,219 @@
+// Draws positioned rectangles, as an SVG document to open in a browser or
+// as ASCII art for the terminal, to see what a layout or a packing did.
+
+use std::fmt::Write;
+
+use super::{Packing, Rect, Rectangle};
+
+// Fill colours handed out in turn to shapes without their own
+const PALETTE: [&str; 8] = ["#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7"];
+
+// Marks shapes on the ASCII canvas, the legend says which is which
+const SYMBOLS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape
+{
+    pub rect: Rectangle,
+    pub label: String,
+    // any SVG colour, a palette colour if None
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Scene
+{
+    // drawn in order, later shapes on top
+    pub shapes: Vec<Shape>,
+    // what's drawn, everything in the shapes if None
+    pub bounds: Option<Rectangle>,
+    // distance between SVG grid lines, no grid if None
+    pub grid: Option<u32>,
+}
+
+impl Scene
+{
+    pub fn new() -> Self
+    {
+        Scene::default()
+    }
+
+    pub fn add(&mut self, rect: Rectangle, label: impl Into<String>) -> &mut Shape
+    {
+        self.shapes.push(Shape { rect, label: label.into(), color: None });
+        self.shapes.last_mut().expect("just pushed")
+    }
+
+    // One bin of a packing, shapes are labelled with their index in the
+    // list that was packed
+    pub fn from_packing(packing: &Packing, bin: usize) -> Self
+    {
+        let mut scene = Scene {
+            bounds: Some(Rectangle::new(packing.config.bin_width, packing.config.bin_height)),
+            ..Scene::default()
+        };
+        for p in packing.placements_in(bin)
+        {
+            let label = if p.rotated { format!("#{} (rotated)", p.index) } else { format!("#{}", p.index) };
+            scene.add(p.rect, label);
+        }
+        scene
+    }
+
+    // What gets drawn, None for a scene without shapes or bounds. Shapes
+    // can reach further than one Rectangle spans, then the view stops at
+    // u32::MAX wide or tall and whatever lies past it is cut off.
+    pub fn view(&self) -> Option<Rectangle>
+    {
+        if self.bounds.is_some()
+        {
+            return self.bounds;
+        }
+
+        let all = self.shapes.iter().map(|s| Rect::<i64>::from(s.rect)).reduce(|all, r| all.union_bounds(&r))?;
+        Some(Rectangle {
+            x: i32::try_from(all.x).expect("the view starts at some shape's x"),
+            y: i32::try_from(all.y).expect("the view starts at some shape's y"),
+            width: u32::try_from(all.width).unwrap_or(u32::MAX),
+            height: u32::try_from(all.height).unwrap_or(u32::MAX),
+        })
+    }
+
+    fn color(&self, i: usize) -> &str
+    {
+        self.shapes[i].color.as_deref().unwrap_or(PALETTE[i % PALETTE.len()])
+    }
+
+    pub fn to_svg(&self) -> String
+    {
+        let view = self.view().unwrap_or_default();
+        let (width, height) = (view.width.max(1), view.height.max(1));
+        // text and margins grow with the scene, so they look the same at any size
+        let unit = f64::from(width.max(height)) / 100.0;
+        let margin = unit * 2.0;
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.2} {:.2} {:.2} {:.2}" width="800" preserveAspectRatio="xMidYMid meet">"#,
+            f64::from(view.x) - margin,
+            f64::from(view.y) - margin,
+            f64::from(width) + 2.0 * margin,
+            f64::from(height) + 2.0 * margin,
+        );
+        let _ = writeln!(svg, r#"  <rect x="{}" y="{}" width="{width}" height="{height}" fill="white" stroke="black" vector-effect="non-scaling-stroke"/>"#, view.x, view.y);
+
+        if let Some(step) = self.grid.filter(|&step| step > 0)
+        {
+            let _ = writeln!(svg, "  <defs>");
+            let _ = writeln!(
+                svg,
+                r#"    <pattern id="grid" x="{}" y="{}" width="{step}" height="{step}" patternUnits="userSpaceOnUse">"#,
+                view.x, view.y
+            );
+            let _ = writeln!(svg, r##"      <path d="M {step} 0 L 0 0 0 {step}" fill="none" stroke="#ccc" vector-effect="non-scaling-stroke"/>"##);
+            let _ = writeln!(svg, "    </pattern>");
+            let _ = writeln!(svg, "  </defs>");
+            let _ = writeln!(svg, r#"  <rect x="{}" y="{}" width="{width}" height="{height}" fill="url(#grid)"/>"#, view.x, view.y);
+        }
+
+        for (i, shape) in self.shapes.iter().enumerate()
+        {
+            let r = &shape.rect;
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="0.6" stroke="black" vector-effect="non-scaling-stroke"><title>{}</title></rect>"#,
+                r.x,
+                r.y,
+                r.width,
+                r.height,
+                escape(self.color(i)),
+                escape(&shape.label),
+            );
+            if !shape.label.is_empty()
+            {
+                let _ = writeln!(
+                    svg,
+                    r#"  <text x="{}" y="{}" font-size="{:.2}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                    r.left() as f64 + f64::from(r.width) / 2.0,
+                    r.top() as f64 + f64::from(r.height) / 2.0,
+                    unit * 3.0,
+                    escape(&shape.label),
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // Draws the scene in at most `columns` x `rows` characters, followed by
+    // a legend. A character is about twice as tall as it is wide, so a row
+    // covers twice the distance a column does.
+    pub fn to_ascii(&self, columns: usize, rows: usize) -> String
+    {
+        let Some(view) = self.view() else {
+            return String::from("(nothing to draw)\n");
+        };
+
+        let (columns, rows) = (columns.max(1) as u64, rows.max(1) as u64);
+        // units per column, the smallest one that fits both ways
+        let scale = u64::from(view.width)
+            .div_ceil(columns)
+            .max(u64::from(view.height).div_ceil(rows * 2))
+            .max(1);
+        let cell = |units: i64| (units - view.left()).div_euclid(scale as i64);
+        let row = |units: i64| (units - view.top()).div_euclid(2 * scale as i64);
+
+        let width = cell(view.right() + scale as i64 - 1).max(1) as usize;
+        let height = row(view.bottom() + 2 * scale as i64 - 1).max(1) as usize;
+        let mut canvas = vec![vec![b' '; width]; height];
+
+        for (i, shape) in self.shapes.iter().enumerate()
+        {
+            let r = &shape.rect;
+            // at least one character, however small the shape is
+            let (x0, x1) = (cell(r.left()), cell(r.right() - 1).max(cell(r.left())));
+            let (y0, y1) = (row(r.top()), row(r.bottom() - 1).max(row(r.top())));
+            let symbol = SYMBOLS[i % SYMBOLS.len()];
+
+            for y in y0.max(0)..=y1.min(height as i64 - 1)
+            {
+                for x in x0.max(0)..=x1.min(width as i64 - 1)
+                {
+                    // outlined when there's room for an inside
+                    let outlined = x1 - x0 >= 2 && y1 - y0 >= 2;
+                    let on_x_edge = x == x0 || x == x1;
+                    let on_y_edge = y == y0 || y == y1;
+                    canvas[y as usize][x as usize] = match (outlined, on_x_edge, on_y_edge)
+                    {
+                        (true, true, true) => b'+',
+                        (true, false, true) => b'-',
+                        (true, true, false) => b'|',
+                        _ => symbol,
+                    };
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for line in &canvas
+        {
+            out.push_str(String::from_utf8_lossy(line).trim_end());
+            out.push('\n');
+        }
+        let _ = writeln!(out, "1 column = {scale}, 1 row = {}", 2 * scale);
+        for (i, shape) in self.shapes.iter().enumerate()
+        {
+            let r = &shape.rect;
+            let entry = format!("{}  {}x{} at ({}, {})  {}", SYMBOLS[i % SYMBOLS.len()] as char, r.width, r.height, r.x, r.y, shape.label);
+            let _ = writeln!(out, "{}", entry.trim_end());
+        }
+        out
+    }
+}
+
+fn escape(text: &str) -> String
+{
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -0,0 +1,291 @@
+// A small text format for writing rectangles down, one per line:
+//
+//   # everything after a '#' is a comment
+//   rect   door   10 20 30 50      name x y width height
+//   square window 50 10 20         name x y size
+//   include "more shapes.txt"      relative to the including file
+//
+// Names must be unique across all included files. Errors point at the
+// file, line and column of what's wrong.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Rectangle;
+
+// includes nested deeper than this are most likely a mistake
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedRect
+{
+    pub name: String,
+    pub rect: Rectangle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapesError
+{
+    // the file, or "<input>" for text that didn't come from one
+    pub origin: String,
+    // both count from 1, column in characters, line is 0 for errors about
+    // the whole file
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    // the line the error is on, to point at the column
+    pub source_line: String,
+}
+
+impl fmt::Display for ShapesError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        // errors about the whole file, like not being able to read it
+        if self.line == 0
+        {
+            return write!(f, "{}: {}", self.origin, self.message);
+        }
+
+        writeln!(f, "{}:{}:{}: {}", self.origin, self.line, self.column, self.message)?;
+        writeln!(f, "    {}", self.source_line)?;
+        write!(f, "    {}^", " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl Error for ShapesError {}
+
+// A word of a line and the column it starts at
+#[derive(Debug, Clone, Copy)]
+struct Token<'a>
+{
+    text: &'a str,
+    column: usize,
+}
+
+// Splits a line into words, "quoted words" may contain spaces and come
+// without their quotes. The column of an unterminated quote is the error.
+fn tokenize(line: &str) -> Result<Vec<Token<'_>>, usize>
+{
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().enumerate().peekable();
+
+    while let Some((column, (start, c))) = chars.next()
+    {
+        if c == '#'
+        {
+            break;
+        }
+        if c.is_whitespace()
+        {
+            continue;
+        }
+
+        let token = if c == '"'
+        {
+            let end = chars.find(|(_, (_, c))| *c == '"').ok_or(column + 1)?.1.0;
+            &line[start + 1..end]
+        }
+        else
+        {
+            let mut end = line.len();
+            while let Some(&(_, (i, c))) = chars.peek()
+            {
+                if c.is_whitespace() || c == '#'
+                {
+                    end = i;
+                    break;
+                }
+                chars.next();
+            }
+            &line[start..end]
+        };
+        tokens.push(Token { text: token, column: column + 1 });
+    }
+
+    Ok(tokens)
+}
+
+struct Parser
+{
+    shapes: Vec<NamedRect>,
+    // where every name was defined, for the duplicate error
+    defined: HashMap<String, (String, usize)>,
+    // files being parsed, to catch includes going in circles
+    including: Vec<PathBuf>,
+}
+
+// Where in which text we are, builds the errors
+struct Position<'a>
+{
+    origin: &'a str,
+    line: usize,
+    text: &'a str,
+}
+
+impl Position<'_>
+{
+    fn error(&self, column: usize, message: impl Into<String>) -> ShapesError
+    {
+        ShapesError {
+            origin: self.origin.to_string(),
+            line: self.line,
+            column,
+            message: message.into(),
+            source_line: self.text.to_string(),
+        }
+    }
+
+    // errors about something missing point right after the line
+    fn end(&self) -> usize
+    {
+        self.text.trim_end().chars().count() + 1
+    }
+}
+
+impl Parser
+{
+    fn new() -> Self
+    {
+        Parser { shapes: Vec::new(), defined: HashMap::new(), including: Vec::new() }
+    }
+
+    fn parse(&mut self, text: &str, origin: &str, dir: &Path) -> Result<(), ShapesError>
+    {
+        for (i, line) in text.lines().enumerate()
+        {
+            let at = Position { origin, line: i + 1, text: line };
+            let tokens = tokenize(line).map_err(|column| at.error(column, "unterminated quote"))?;
+            let Some((command, args)) = tokens.split_first() else {
+                continue;
+            };
+
+            match command.text
+            {
+                "rect" => {
+                    let [name, x, y, width, height] = fields(&at, command, args, ["name", "x", "y", "width", "height"])?;
+                    let rect = Rectangle::at(number(&at, x, "x")?, number(&at, y, "y")?, number(&at, width, "width")?, number(&at, height, "height")?);
+                    self.define(&at, name, rect)?;
+                }
+                "square" => {
+                    let [name, x, y, size] = fields(&at, command, args, ["name", "x", "y", "size"])?;
+                    let size = number(&at, size, "size")?;
+                    let rect = Rectangle::at(number(&at, x, "x")?, number(&at, y, "y")?, size, size);
+                    self.define(&at, name, rect)?;
+                }
+                "include" => {
+                    let [file] = fields(&at, command, args, ["file"])?;
+                    let path = dir.join(file.text);
+                    self.include(&at, file, &path)?;
+                }
+                other => {
+                    return Err(at.error(command.column, format!("unknown command '{other}', expected rect, square or include")));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn define(&mut self, at: &Position, name: Token, rect: Rectangle) -> Result<(), ShapesError>
+    {
+        if let Some((origin, line)) = self.defined.get(name.text)
+        {
+            let place = if origin == at.origin { format!("line {line}") } else { format!("{origin}:{line}") };
+            return Err(at.error(name.column, format!("'{}' is already defined at {place}", name.text)));
+        }
+
+        self.defined.insert(name.text.to_string(), (at.origin.to_string(), at.line));
+        self.shapes.push(NamedRect { name: name.text.to_string(), rect });
+        Ok(())
+    }
+
+    fn include(&mut self, at: &Position, file: Token, path: &Path) -> Result<(), ShapesError>
+    {
+        // canonical, so the same file reached two ways is still a cycle
+        let canonical = fs::canonicalize(path).map_err(|e| at.error(file.column, format!("can't include {}: {e}", path.display())))?;
+        if self.including.contains(&canonical)
+        {
+            return Err(at.error(file.column, format!("{} includes itself", path.display())));
+        }
+        if self.including.len() >= MAX_INCLUDE_DEPTH
+        {
+            return Err(at.error(file.column, format!("includes nested more than {MAX_INCLUDE_DEPTH} deep")));
+        }
+
+        self.load(path, canonical).map_err(|e| match e
+        {
+            // the file itself couldn't be read, that's the include's fault
+            LoadError::Io(e) => at.error(file.column, format!("can't include {}: {e}", path.display())),
+            LoadError::Parse(e) => e,
+        })
+    }
+
+    fn load(&mut self, path: &Path, canonical: PathBuf) -> Result<(), LoadError>
+    {
+        let text = fs::read_to_string(path).map_err(LoadError::Io)?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        self.including.push(canonical);
+        let result = self.parse(&text, &path.display().to_string(), dir);
+        self.including.pop();
+        result.map_err(LoadError::Parse)
+    }
+}
+
+enum LoadError
+{
+    Io(std::io::Error),
+    Parse(ShapesError),
+}
+
+// Exactly as many arguments as there are names for them
+fn fields<'a, const N: usize>(at: &Position, command: &Token, args: &[Token<'a>], names: [&str; N]) -> Result<[Token<'a>; N], ShapesError>
+{
+    let usage = format!("{} {}", command.text, names.join(" "));
+    if let Some(extra) = args.get(N)
+    {
+        return Err(at.error(extra.column, format!("unexpected '{}', usage: {usage}", extra.text)));
+    }
+
+    <[Token; N]>::try_from(args).map_err(|_| at.error(at.end(), format!("missing {}, usage: {usage}", names[args.len()])))
+}
+
+fn number<T: std::str::FromStr>(at: &Position, token: Token, what: &str) -> Result<T, ShapesError>
+{
+    token
+        .text
+        .parse()
+        .map_err(|_| at.error(token.column, format!("expected a whole number for {what}, found '{}'", token.text)))
+}
+
+// Rectangles written in `text`, includes are looked for in the current
+// directory
+pub fn parse_shapes(text: &str) -> Result<Vec<NamedRect>, ShapesError>
+{
+    let mut parser = Parser::new();
+    parser.parse(text, "<input>", Path::new("."))?;
+    Ok(parser.shapes)
+}
+
+pub fn load_shapes(path: &Path) -> Result<Vec<NamedRect>, ShapesError>
+{
+    let mut parser = Parser::new();
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+
+    match parser.load(path, canonical)
+    {
+        Ok(()) => Ok(parser.shapes),
+        Err(LoadError::Parse(e)) => Err(e),
+        Err(LoadError::Io(e)) => Err(ShapesError {
+            origin: path.display().to_string(),
+            line: 0,
+            column: 0,
+            message: format!("can't read: {e}"),
+            source_line: String::new(),
+        }),
+    }
+}
@@ -0,0 +1,149 @@
+// Affine transforms: anything made of moving, scaling, rotating and
+// shearing. A transform maps (x, y) to
+//
+//   x' = a x + c y + e
+//   y' = b x + d y + f
+//
+// the same six numbers as SVG's matrix(a b c d e f). Whatever it's applied
+// to comes out in f64, Rectangle aside, which gets the cells covering it.
+
+use super::{Point, Polygon, Rect, Rectangle, Scalar};
+
+// how far from a whole number still counts as one, so a rectangle turned
+// by 90° doesn't grow by a cell for 10.000000000000002
+const SNAP: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform
+{
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for Transform
+{
+    fn default() -> Self
+    {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform
+{
+    // leaves everything where it is
+    pub const IDENTITY: Transform = Transform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub fn translate(dx: f64, dy: f64) -> Self
+    {
+        Transform { e: dx, f: dy, ..Transform::IDENTITY }
+    }
+
+    // From the origin, negative factors mirror
+    pub fn scale(sx: f64, sy: f64) -> Self
+    {
+        Transform { a: sx, d: sy, ..Transform::IDENTITY }
+    }
+
+    // Around the origin, counter clockwise (with y going up) for positive
+    // angles, in radians
+    pub fn rotate(angle: f64) -> Self
+    {
+        let (sin, cos) = angle.sin_cos();
+        Transform { a: cos, b: sin, c: -sin, d: cos, e: 0.0, f: 0.0 }
+    }
+
+    pub fn rotate_around(angle: f64, center: Point<f64>) -> Self
+    {
+        Transform::translate(-center.x, -center.y).then(&Transform::rotate(angle)).then(&Transform::translate(center.x, center.y))
+    }
+
+    // x moves by kx for every step up, y by ky for every step right
+    pub fn shear(kx: f64, ky: f64) -> Self
+    {
+        Transform { b: ky, c: kx, ..Transform::IDENTITY }
+    }
+
+    // This one first, then `next`
+    pub fn then(&self, next: &Transform) -> Transform
+    {
+        Transform {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: next.a * self.e + next.c * self.f + next.e,
+            f: next.b * self.e + next.d * self.f + next.f,
+        }
+    }
+
+    // How much areas grow, negative when the transform mirrors
+    pub fn determinant(&self) -> f64
+    {
+        self.a * self.d - self.b * self.c
+    }
+
+    // The transform undoing this one, None for those squashing everything
+    // onto a line or a point
+    pub fn inverse(&self) -> Option<Transform>
+    {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite()
+        {
+            return None;
+        }
+
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Transform { a, b, c, d, e: -(a * self.e + c * self.f), f: -(b * self.e + d * self.f) })
+    }
+
+    pub fn apply_point<T: Scalar>(&self, p: Point<T>) -> Point<f64>
+    {
+        let (x, y) = (p.x.to_f64(), p.y.to_f64());
+        Point::new(self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+
+    // The smallest axis aligned rectangle holding the transformed one,
+    // exactly it when nothing's rotated or sheared
+    pub fn apply_rect<P: Scalar, S: Scalar>(&self, r: &Rectangle<P, S>) -> Rect<f64>
+    {
+        let origin = Point::new(r.x.to_f64(), r.y.to_f64());
+        let end = origin + Point::new(r.width.to_f64(), r.height.to_f64());
+        let corners = [origin, Point::new(end.x, origin.y), end, Point::new(origin.x, end.y)].map(|p| self.apply_point(p));
+
+        let (mut low, mut high) = (corners[0], corners[0]);
+        for p in &corners[1..]
+        {
+            low = Point::new(low.x.min(p.x), low.y.min(p.y));
+            high = Point::new(high.x.max(p.x), high.y.max(p.y));
+        }
+        Rect::from_corners(low, high)
+    }
+
+    // The cells covering the transformed rectangle, None if they don't fit
+    // in a Rectangle
+    pub fn apply_rectangle(&self, r: &Rectangle) -> Option<Rectangle>
+    {
+        let bounds = self.apply_rect(r);
+        let end = bounds.end();
+
+        let snap = |v: f64| if (v - v.round()).abs() < SNAP { v.round() } else { v };
+        let edge = |v: f64| (v.is_finite() && v.abs() < i64::MAX as f64).then_some(v as i64);
+        Rectangle::from_edges(
+            edge(snap(bounds.x).floor())?,
+            edge(snap(bounds.y).floor())?,
+            edge(snap(end.x).ceil())?,
+            edge(snap(end.y).ceil())?,
+        )
+    }
+
+    // Every vertex transformed; a mirroring transform turns counter
+    // clockwise polygons clockwise
+    pub fn apply_polygon<T: Scalar>(&self, polygon: &Polygon<T>) -> Polygon<f64>
+    {
+        Polygon::new(polygon.vertices.iter().map(|&p| self.apply_point(p)).collect())
+    }
+}
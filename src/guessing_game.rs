@@ -0,0 +1,680 @@
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+mod engine;
+mod guesser;
+mod network;
+mod scoreboard;
+mod session;
+mod tournament;
+mod variants;
+
+pub use engine::{
+    CapturedOutput,
+    Game,
+    GameState,
+    GuessError,
+    InputSource,
+    Move,
+    OutputSink,
+    ScriptedInput,
+    Silent,
+    StdinInput,
+    StdoutSink,
+};
+pub use guesser::{bot_by_name, BinarySearchBot, Guesser, HumanGuesser, NoisyBot, RandomBot, BOT_NAMES};
+pub use network::{play_remote, serve, ClientMsg, ServerMsg};
+pub use scoreboard::{ScoreEntry, Scoreboard};
+pub use session::{Outcome, Session};
+pub use tournament::BotReport;
+pub use variants::Mode;
+
+const DEFAULT_SCORES_FILE: &str = "guessing_game_scores.txt";
+
+const USAGE: &str = "\
+Usage: guessing_game [OPTIONS]
+       guessing_game serve [--port N] [--players N] [OPTIONS]
+       guessing_game join [--addr HOST:PORT] [--name NAME] [--bot NAME]
+       guessing_game replay FILE [--speed N | --instant]
+
+Options:
+  -m, --mode MODE         classic (too small/too big), hotcold (how close),
+                          mastermind (crack a code) or grid (find a cell)
+  -d, --difficulty LEVEL  easy (1-10), normal (1-50) or hard (1-100, 7 attempts),
+                          mastermind uses 3, 4 or 5 pegs of 4, 6 or 8 colours
+      --min N             smallest possible secret number
+      --max N             largest possible secret number
+      --attempts N        maximum number of guesses, 0 means unlimited
+      --seed N            seed the random generator for a reproducible game
+      --name NAME         name recorded on the scoreboard (default: $USER)
+      --scores FILE       scoreboard file (default: guessing_game_scores.txt)
+      --top               print the top 10 of every difficulty and exit
+      --bot NAME          let a bot play instead: binary, random or noisy
+      --tournament N      every bot (or just --bot) plays N seeded games
+      --record FILE       save every guess, its feedback and time for replay
+      --speed N           replay N times as fast as the game was played
+      --instant           replay without waiting between guesses
+  -h, --help              print this help and exit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Difficulty
+{
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty
+{
+    // (min, max, max_attempts) of the preset
+    fn preset(self) -> (u32, u32, Option<u32>)
+    {
+        match self
+        {
+            Difficulty::Easy => (1, 10, None),
+            Difficulty::Normal => (1, 50, None),
+            Difficulty::Hard => (1, 100, Some(7)),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let name = match self
+        {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Difficulty
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.to_lowercase().as_str()
+        {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("unknown difficulty '{s}', expected easy, normal or hard")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameConfig
+{
+    pub mode: Mode,
+    pub difficulty: Difficulty,
+    pub min: u32,
+    pub max: u32,
+    // None means the player can guess until they get it right
+    pub max_attempts: Option<u32>,
+    pub seed: Option<u64>,
+}
+
+impl Default for GameConfig
+{
+    fn default() -> Self
+    {
+        GameConfig::with_difficulty(Difficulty::default())
+    }
+}
+
+impl GameConfig
+{
+    pub fn with_difficulty(difficulty: Difficulty) -> Self
+    {
+        let (min, max, max_attempts) = difficulty.preset();
+        GameConfig { mode: Mode::default(), difficulty, min, max, max_attempts, seed: None }
+    }
+
+    // --min/--max/--attempts override the preset, no matter where they
+    // appear relative to --difficulty
+    pub fn from_args(args: &[String]) -> Result<GameConfig, String>
+    {
+        let mut mode = Mode::default();
+        let mut difficulty = Difficulty::default();
+        let mut min = None;
+        let mut max = None;
+        let mut attempts = None;
+        let mut seed = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next()
+        {
+            let mut value = || iter.next().ok_or_else(|| format!("option '{arg}' needs a value"));
+
+            match arg.as_str()
+            {
+                "-m" | "--mode" => mode = value()?.parse()?,
+                "-d" | "--difficulty" => difficulty = value()?.parse()?,
+                "--min" => min = Some(parse_number(arg, value()?)?),
+                "--max" => max = Some(parse_number(arg, value()?)?),
+                "--attempts" => attempts = Some(parse_number(arg, value()?)?),
+                "--seed" => seed = Some(parse_number(arg, value()?)?),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        let mut config = GameConfig::with_difficulty(difficulty);
+        config.min = min.unwrap_or(config.min);
+        config.max = max.unwrap_or(config.max);
+        if let Some(attempts) = attempts
+        {
+            config.max_attempts = if attempts == 0 { None } else { Some(attempts) };
+        }
+        config.mode = mode;
+        config.seed = seed;
+
+        if config.min > config.max
+        {
+            return Err(format!("--min {} is larger than --max {}", config.min, config.max));
+        }
+
+        Ok(config)
+    }
+
+    // Scoreboard category: games changed with --min/--max/--attempts aren't
+    // comparable with the preset, they are ranked separately. So are the
+    // other modes, "classic" is left out to keep old scoreboards valid.
+    pub fn category(&self) -> String
+    {
+        let (min, max, max_attempts) = self.difficulty.preset();
+        let category = if (min, max, max_attempts) == (self.min, self.max, self.max_attempts)
+        {
+            self.difficulty.to_string()
+        }
+        else
+        {
+            String::from("custom")
+        };
+
+        match self.mode
+        {
+            Mode::Classic => category,
+            mode => format!("{mode}-{category}"),
+        }
+    }
+
+    // How many guesses a perfect player needs in the worst case, the par
+    // for scoring
+    pub fn par(&self) -> u32
+    {
+        // binary search over `size` numbers
+        let bits = |size: u64| u64::BITS - size.leading_zeros();
+
+        match self.mode
+        {
+            Mode::Classic => bits(u64::from(self.max - self.min) + 1),
+            // distance without a direction is worth less than Less/Greater
+            Mode::HotCold => bits(u64::from(self.max - self.min) + 1) + 2,
+            // Knuth's algorithm needs 5 guesses for 4 pegs of 6 colours
+            Mode::Mastermind => variants::mastermind_shape(self.difficulty).0 + 1,
+            // both coordinates are binary searched at the same time
+            Mode::Grid => bits(u64::from(variants::grid_side(self))),
+        }
+    }
+
+    pub fn pick_secret(&self) -> u32
+    {
+        // seeded games always pick the same secret, which makes them
+        // reproducible
+        match self.seed
+        {
+            Some(seed) => StdRng::seed_from_u64(seed).gen_range(self.min..=self.max),
+            None => rand::thread_rng().gen_range(self.min..=self.max),
+        }
+    }
+}
+
+fn parse_number<T: FromStr>(flag: &str, value: &str) -> Result<T, String>
+{
+    value
+        .parse()
+        .map_err(|_| format!("option '{flag}' expects a non-negative number, got '{value}'"))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult
+{
+    // as the player would have typed it
+    pub secret: String,
+    pub attempts: u32,
+    pub won: bool,
+    pub score: u32,
+    pub duration: Duration,
+}
+
+// 100 points for needing exactly as many guesses as binary search would in
+// the worst case, more for being luckier than that, fewer for every guess
+// above it and nothing for losing
+pub fn score(config: &GameConfig, attempts: u32, won: bool) -> u32
+{
+    if !won || attempts == 0
+    {
+        return 0;
+    }
+
+    config.par() * 100 / attempts
+}
+
+// Everything the `game` subcommand can be asked to do, the game itself is
+// configured by the GameConfig inside
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameOptions
+{
+    pub config: GameConfig,
+    pub name: String,
+    pub scores_file: PathBuf,
+    pub show_top: bool,
+    pub bot: Option<String>,
+    // number of games every bot plays
+    pub tournament: Option<u32>,
+    pub record: Option<PathBuf>,
+}
+
+impl GameOptions
+{
+    pub fn from_args(args: &[String]) -> Result<GameOptions, String>
+    {
+        let mut name = env::var("USER").unwrap_or_else(|_| String::from("anonymous"));
+        let mut scores_file = PathBuf::from(DEFAULT_SCORES_FILE);
+        let mut show_top = false;
+        let mut bot = None;
+        let mut tournament = None;
+        let mut record = None;
+        let mut game_args = Vec::new();
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next()
+        {
+            let mut value = || iter.next().ok_or_else(|| format!("option '{arg}' needs a value"));
+
+            match arg.as_str()
+            {
+                "--name" => name = value()?.clone(),
+                "--scores" => scores_file = PathBuf::from(value()?),
+                "--top" => show_top = true,
+                "--bot" => {
+                    let value = value()?;
+                    if !BOT_NAMES.contains(&value.as_str())
+                    {
+                        return Err(format!("unknown bot '{value}', expected one of {}", BOT_NAMES.join(", ")));
+                    }
+                    bot = Some(value.clone());
+                }
+                "--tournament" => tournament = Some(parse_number(arg, value()?)?),
+                "--record" => record = Some(PathBuf::from(value()?)),
+                // the rest is for GameConfig, together with its value
+                _ => {
+                    game_args.push(arg.clone());
+                    if arg.starts_with('-')
+                    {
+                        game_args.extend(iter.next().cloned());
+                    }
+                }
+            }
+        }
+
+        let config = GameConfig::from_args(&game_args)?;
+
+        // bots only know how to handle too small and too big
+        if config.mode != Mode::Classic && (bot.is_some() || tournament.is_some())
+        {
+            return Err(format!("bots can't play {}, only classic", config.mode));
+        }
+        if record.is_some() && (config.mode != Mode::Classic || tournament.is_some())
+        {
+            return Err(String::from("--record only works for a single classic game"));
+        }
+
+        Ok(GameOptions { config, name, scores_file, show_top, bot, tournament, record })
+    }
+}
+
+// Options of any subcommand that are followed by a value
+const VALUE_OPTIONS: &[&str] = &[
+    "-m", "--mode", "-d", "--difficulty", "--min", "--max", "--attempts", "--seed", "--name", "--scores", "--bot",
+    "--tournament", "--record", "--port", "--players", "--addr", "--speed",
+];
+
+// -h or --help in option position. Like in minigrep, an option's value is
+// never taken for it, `--name -h` is a player called -h. Help still wins
+// over invalid arguments.
+fn help_requested(args: &[String]) -> bool
+{
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next()
+    {
+        if arg == "-h" || arg == "--help"
+        {
+            return true;
+        }
+        if VALUE_OPTIONS.contains(&arg.as_str())
+        {
+            iter.next();
+        }
+    }
+    false
+}
+
+pub fn game_main(args: &[String]) -> ExitCode
+{
+    if help_requested(args)
+    {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match args.first().map(String::as_str)
+    {
+        Some("serve") => return network::serve_main(&args[1..]),
+        Some("join") => return network::join_main(&args[1..]),
+        Some("replay") => return session::replay_main(&args[1..]),
+        _ => (),
+    }
+
+    let options = match GameOptions::from_args(args)
+    {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("guessing game: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let config = &options.config;
+
+    // bots get their randomness from --seed too, so whole tournaments can
+    // be replayed
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+
+    if let Some(games) = options.tournament
+    {
+        let names = match &options.bot
+        {
+            Some(bot) => vec![bot.as_str()],
+            None => BOT_NAMES.to_vec(),
+        };
+        let mut bots: Vec<Box<dyn Guesser>> = names
+            .iter()
+            .filter_map(|name| bot_by_name(name, seed))
+            .collect();
+
+        let reports = tournament::run(config, &mut bots, games, seed);
+        print!("{}", tournament::render(config, &reports));
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some(bot) = &options.bot
+    {
+        let mut bot = bot_by_name(bot, seed).expect("bot names are checked while parsing");
+        let result = match &options.record
+        {
+            Some(path) => {
+                let player = bot.name().to_string();
+                session::record(config, bot.as_mut(), &player, path)
+            }
+            None => play_with(config, bot.as_mut(), &mut StdoutSink),
+        };
+        return if result.won { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+
+    let (mut board, warnings) = Scoreboard::load(&options.scores_file);
+    for warning in warnings
+    {
+        eprintln!("scoreboard: {warning}");
+    }
+
+    if options.show_top
+    {
+        print!("{}", board.render());
+        return ExitCode::SUCCESS;
+    }
+
+    let result = match &options.record
+    {
+        Some(path) => session::record(config, &mut HumanGuesser::stdin(), &options.name, path),
+        None => play(config),
+    };
+    if !result.won
+    {
+        return ExitCode::FAILURE;
+    }
+
+    board.record(ScoreEntry {
+        category: config.category(),
+        name: options.name.clone(),
+        min: config.min,
+        max: config.max,
+        attempts: result.attempts,
+        duration: result.duration,
+        score: result.score,
+    });
+
+    // a scoreboard that can't be written shouldn't spoil the win
+    match board.save(&options.scores_file)
+    {
+        Ok(()) => print!("\n{}", board.render()),
+        Err(e) => eprintln!("scoreboard: could not save {}: {e}", options.scores_file.display()),
+    }
+
+    ExitCode::SUCCESS
+}
+
+// The game of chapter 2, as the book writes it. Everything above grew out of
+// it: play() is the same game on the engine, with settings and a scoreboard.
+pub fn chapter_2_guessing_game()
+{
+    println!("Guess the number!");
+
+    // 1..=100 is INCLUSIVE RANGE, meaning we include both 1 and 100
+    // and create an array of numbers from 1 to 100
+    // 1..100 is EXCLUSIVE RANGE, 100 is not included
+    // gen_range method takes range as argument (start..=end)
+    let secret_number = rand::thread_rng().gen_range(1..=50);
+
+    // loop creates infty loop
+    loop {
+        println!("Please input your guess.");
+
+        let mut guess = String::new();
+
+        let read = io::stdin()
+            .read_line(&mut guess)
+            .expect("Failed to read line");
+
+        // not in the book: with stdin closed read_line keeps reading
+        // nothing, and the loop would never end
+        if read == 0
+        {
+            println!("No more input, the number was {secret_number}");
+            break;
+        }
+
+        // We need to convert guess: String to u32 - secret_nbr type
+        // We SHADOW previous guess with a new one.
+        // - trim() --> eliminate any whitespace at the beginning and end
+        // - parse() --> parse method on strings converts a string to another 
+        //               type; we need to specify this type after :
+        //               Because parse might fail, the parse method returns a 
+        //               Result type. If it returns **Err** expect will catch 
+        //               it, otherwise it will return **Ok** 
+        // - expect() --> instance of RESULT has expect method so if Result is 
+        //                Err expect will cause programme to crash
+        // 
+        // old version: our programme crashed when encountered not a number
+        // let guess: u32 = guess.trim().parse().expect("Please type a number");
+
+        // new version: we use pattern matching to handle Err case
+        let guess: u32 = match guess.trim().parse() 
+        {
+            Ok(num) => {
+                num
+            },
+            Err(_) => {
+                println!("Type a number!");
+                continue;
+            }
+        };
+
+        // Like in haskell, we have pattern matching , 
+        // cmp returns Less, Greater of Equal value
+        // More formally: A match expression is made up of arms. An arm consists of 
+        // a pattern to match against, and the code that should be run if the value 
+        // given to match fits that arm’s pattern
+        match guess.cmp(&secret_number)
+        {
+            Ordering::Less => println!("Too small"),
+            Ordering::Greater => println!("Too big"),
+            Ordering::Equal => {
+                println!("You've guessed right!");
+                break;
+            }
+        }
+        println!("---------------------");
+    }
+}
+
+// A person playing through stdin
+pub fn play(config: &GameConfig) -> GameResult
+{
+    play_with(config, &mut HumanGuesser::stdin(), &mut StdoutSink)
+}
+
+// One game of `guesser` against a freshly picked secret number, told to
+// `out`. Tournaments pass Silent, nobody watches those.
+pub fn play_with(config: &GameConfig, guesser: &mut dyn Guesser, out: &mut dyn OutputSink) -> GameResult
+{
+    play_game(config, guesser, out).result()
+}
+
+// play_with, but hands back the finished game with all of its guesses
+pub fn play_game(config: &GameConfig, guesser: &mut dyn Guesser, out: &mut dyn OutputSink) -> Game
+{
+    let mut game = match config.mode
+    {
+        Mode::Classic => Game::new(config, config.pick_secret()),
+        _ => Game::with_puzzle(config, variants::puzzle(config)),
+    };
+    engine::run(&mut game, guesser, out);
+    game
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn args(line: &str) -> Vec<String>
+    {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn difficulty_presets()
+    {
+        let presets = [("easy", 1, 10, None), ("normal", 1, 50, None), ("hard", 1, 100, Some(7))];
+        for (name, min, max, max_attempts) in presets
+        {
+            let config = GameConfig::from_args(&args(&format!("-d {name}"))).unwrap();
+            assert_eq!((config.min, config.max, config.max_attempts), (min, max, max_attempts), "{name}");
+            assert_eq!(config.category(), name);
+        }
+        assert_eq!(GameConfig::from_args(&[]).unwrap(), GameConfig::default());
+
+        // the overrides win, wherever they are, and make the game custom
+        let config = GameConfig::from_args(&args("--max 20 --attempts 0 -d hard")).unwrap();
+        assert_eq!((config.min, config.max, config.max_attempts), (1, 20, None));
+        assert_eq!(config.category(), "custom");
+
+        assert!(GameConfig::from_args(&args("-d impossible")).is_err());
+    }
+
+    #[test]
+    fn help_only_in_option_position()
+    {
+        assert!(help_requested(&args("-h")));
+        assert!(help_requested(&args("--seed 3 --help")));
+        assert!(help_requested(&args("serve --port 1 -h")));
+        assert!(help_requested(&args("--bogus -h")));
+
+        // values that look like help
+        assert!(!help_requested(&args("--name -h")));
+        assert!(!help_requested(&args("join --addr --help --name -h")));
+        assert!(!help_requested(&args("--record -h --seed 1")));
+        assert_eq!(GameOptions::from_args(&args("--name -h")).unwrap().name, "-h");
+    }
+
+    #[test]
+    fn min_above_max_is_rejected()
+    {
+        assert_eq!(GameConfig::from_args(&args("--min 10 --max 9")), Err(String::from("--min 10 is larger than --max 9")));
+        // above the preset's max counts too
+        assert!(GameConfig::from_args(&args("-d easy --min 11")).is_err());
+        assert!(GameConfig::from_args(&args("--min 5 --max 5")).is_ok());
+    }
+
+    #[test]
+    fn seed_picks_the_same_secret()
+    {
+        let config = GameConfig::from_args(&args("--min 1 --max 1000000 --seed 42")).unwrap();
+        let secret = config.pick_secret();
+        assert!((1..=1_000_000).contains(&secret));
+        assert!((0..10).all(|_| config.pick_secret() == secret));
+
+        // other seeds, other secrets
+        assert!((43..53).any(|seed| GameConfig { seed: Some(seed), ..config.clone() }.pick_secret() != secret));
+
+        // a single number leaves no choice
+        let fixed = GameConfig { min: 7, max: 7, ..GameConfig::default() };
+        assert_eq!(fixed.pick_secret(), 7);
+    }
+
+    #[test]
+    fn par_at_the_range_extremes()
+    {
+        let par = |min, max, mode| GameConfig { min, max, mode, ..GameConfig::default() }.par();
+
+        assert_eq!(par(5, 5, Mode::Classic), 1);
+        assert_eq!(par(1, 2, Mode::Classic), 2);
+        assert_eq!(par(1, 50, Mode::Classic), 6);
+        assert_eq!(par(1, 64, Mode::Classic), 7);
+        assert_eq!(par(0, u32::MAX, Mode::Classic), 33);
+        assert_eq!(par(0, u32::MAX, Mode::HotCold), 35);
+    }
+
+    #[test]
+    fn scores()
+    {
+        let config = GameConfig::default();
+        assert_eq!(config.par(), 6);
+
+        // losing is worth nothing, however few guesses it took
+        assert_eq!(score(&config, 3, false), 0);
+        assert_eq!(score(&config, 0, true), 0);
+
+        assert_eq!(score(&config, 6, true), 100);
+        assert_eq!(score(&config, 2, true), 300);
+        // over par
+        assert_eq!(score(&config, 12, true), 50);
+        assert_eq!(score(&config, 7, true), 85);
+    }
+}
@@ -0,0 +1,503 @@
+// The game itself, without any IO.
+//
+// Game is the state machine: it knows the secret, counts attempts and
+// decides when the game is over, for the classic game and for the variants
+// alike. Everything it reads comes through an
+// InputSource and everything it prints goes to an OutputSink, so the same
+// game runs on a terminal, on scripted lines in tests or on a recorded
+// session.
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use super::{score, GameConfig, GameResult, Guesser};
+
+// Where a person's answers come from
+pub trait InputSource
+{
+    // None means there is nothing more to read
+    fn read_line(&mut self) -> Option<String>;
+}
+
+// Where the game's messages go
+pub trait OutputSink
+{
+    fn say(&mut self, msg: &str);
+}
+
+pub struct StdinInput;
+
+impl InputSource for StdinInput
+{
+    fn read_line(&mut self) -> Option<String>
+    {
+        read_line_from(&mut io::stdin().lock())
+    }
+}
+
+// A line from `reader`, None once it's closed. A line that isn't UTF-8 or a
+// broken reader ends the input too, the game can't go on without it.
+fn read_line_from(reader: &mut impl BufRead) -> Option<String>
+{
+    let mut line = String::new();
+    match reader.read_line(&mut line)
+    {
+        // Ok(0) when stdin is closed
+        Ok(0) => None,
+        Ok(_) => Some(line),
+        Err(e) => {
+            eprintln!("guessing game: can't read the input: {e}");
+            None
+        }
+    }
+}
+
+// Hands out prepared lines one by one, then behaves like a closed stdin
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedInput
+{
+    lines: VecDeque<String>,
+}
+
+impl ScriptedInput
+{
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ScriptedInput { lines: lines.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl InputSource for ScriptedInput
+{
+    fn read_line(&mut self) -> Option<String>
+    {
+        self.lines.pop_front()
+    }
+}
+
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink
+{
+    fn say(&mut self, msg: &str)
+    {
+        println!("{msg}");
+    }
+}
+
+// Swallows everything, for games nobody watches like tournaments
+pub struct Silent;
+
+impl OutputSink for Silent
+{
+    fn say(&mut self, _msg: &str) {}
+}
+
+// Keeps every message, so tests can look at what the player saw
+#[derive(Debug, Clone, Default)]
+pub struct CapturedOutput
+{
+    pub lines: Vec<String>,
+}
+
+impl OutputSink for CapturedOutput
+{
+    fn say(&mut self, msg: &str)
+    {
+        self.lines.push(msg.to_string());
+    }
+}
+
+// What a variant has to say about a guess, the classic game's secret is
+// just the number in Game
+pub(crate) trait Puzzle: fmt::Debug
+{
+    fn rules(&self) -> String;
+
+    // the feedback for the player and whether the guess solved it, Err is
+    // a message about input that doesn't count as a guess
+    fn check(&self, guess: &str) -> Result<(String, bool), String>;
+
+    // the secret as the player would have typed it
+    fn secret(&self) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameState
+{
+    Playing,
+    Won,
+    // out of attempts or given up
+    Lost,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuessError
+{
+    // outside of config.min..=config.max, doesn't cost an attempt
+    OutOfRange,
+    GameOver,
+}
+
+// One counted guess, `at` is measured from the start of the game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move
+{
+    pub guess: u32,
+    pub ordering: Ordering,
+    pub at: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game
+{
+    config: GameConfig,
+    secret: u32,
+    // the variant being played, None for the classic game
+    puzzle: Option<Rc<dyn Puzzle>>,
+    attempts: u32,
+    state: GameState,
+    // every counted guess with its feedback, in order
+    history: Vec<Move>,
+    start: Instant,
+    // how long the game took, set once it is over
+    finished: Option<Duration>,
+}
+
+impl Game
+{
+    pub fn new(config: &GameConfig, secret: u32) -> Self
+    {
+        Game {
+            config: config.clone(),
+            secret,
+            puzzle: None,
+            attempts: 0,
+            state: GameState::Playing,
+            history: Vec::new(),
+            start: Instant::now(),
+            finished: None,
+        }
+    }
+
+    // A variant, guessed with Game::answer instead of Game::guess
+    pub(crate) fn with_puzzle(config: &GameConfig, puzzle: Box<dyn Puzzle>) -> Self
+    {
+        Game { puzzle: Some(Rc::from(puzzle)), ..Game::new(config, 0) }
+    }
+
+    pub fn config(&self) -> &GameConfig
+    {
+        &self.config
+    }
+
+    // 0 for the variants, see secret_text
+    pub fn secret(&self) -> u32
+    {
+        self.secret
+    }
+
+    // the secret as the player would have typed it
+    pub fn secret_text(&self) -> String
+    {
+        match &self.puzzle
+        {
+            Some(puzzle) => puzzle.secret(),
+            None => self.secret.to_string(),
+        }
+    }
+
+    pub fn state(&self) -> GameState
+    {
+        self.state
+    }
+
+    pub fn history(&self) -> &[Move]
+    {
+        &self.history
+    }
+
+    pub fn guess(&mut self, guess: u32) -> Result<Ordering, GuessError>
+    {
+        // variants have no number to compare with
+        if self.state != GameState::Playing || self.puzzle.is_some()
+        {
+            return Err(GuessError::GameOver);
+        }
+        if guess < self.config.min || guess > self.config.max
+        {
+            return Err(GuessError::OutOfRange);
+        }
+
+        let ordering = guess.cmp(&self.secret);
+        self.history.push(Move { guess, ordering, at: self.elapsed() });
+        self.count(ordering == Ordering::Equal);
+
+        Ok(ordering)
+    }
+
+    // A guess at a variant: the feedback and whether it solved the puzzle,
+    // Err is a message about input that doesn't count as a guess
+    pub fn answer(&mut self, guess: &str) -> Result<(String, bool), String>
+    {
+        let Some(puzzle) = self.puzzle.clone() else {
+            return Err(String::from("Type a number!"));
+        };
+        if self.state != GameState::Playing
+        {
+            return Err(String::from("The game is over!"));
+        }
+
+        let (feedback, solved) = puzzle.check(guess)?;
+        self.count(solved);
+        Ok((feedback, solved))
+    }
+
+    // one more attempt, which may end the game
+    fn count(&mut self, solved: bool)
+    {
+        self.attempts += 1;
+        if solved
+        {
+            self.finish(GameState::Won);
+        }
+        else if self.config.max_attempts.is_some_and(|max| self.attempts >= max)
+        {
+            self.finish(GameState::Lost);
+        }
+    }
+
+    pub fn give_up(&mut self)
+    {
+        if self.state == GameState::Playing
+        {
+            self.finish(GameState::Lost);
+        }
+    }
+
+    fn finish(&mut self, state: GameState)
+    {
+        self.state = state;
+        self.finished = Some(self.start.elapsed());
+    }
+
+    pub fn elapsed(&self) -> Duration
+    {
+        self.finished.unwrap_or_else(|| self.start.elapsed())
+    }
+
+    pub fn result(&self) -> GameResult
+    {
+        let won = self.state == GameState::Won;
+        GameResult {
+            secret: self.secret_text(),
+            attempts: self.attempts,
+            won,
+            score: score(&self.config, self.attempts, won),
+            duration: self.elapsed(),
+        }
+    }
+}
+
+// Plays `game` to the end with `guesser`, telling `out` what happens
+pub fn run(game: &mut Game, guesser: &mut dyn Guesser, out: &mut dyn OutputSink) -> GameResult
+{
+    let config = game.config().clone();
+    let puzzle = game.puzzle.clone();
+
+    let what = match &puzzle
+    {
+        None => {
+            out.say("Guess the number!");
+            match config.max_attempts
+            {
+                Some(max) => out.say(&format!("The number is between {} and {}, you have {max} attempts.", config.min, config.max)),
+                None => out.say(&format!("The number is between {} and {}.", config.min, config.max)),
+            }
+            "number"
+        }
+        Some(puzzle) => {
+            out.say(&format!("Welcome to {}!", config.mode));
+            match config.max_attempts
+            {
+                Some(max) => out.say(&format!("{} You have {max} attempts.", puzzle.rules())),
+                None => out.say(&puzzle.rules()),
+            }
+            "answer"
+        }
+    };
+
+    guesser.start(&config);
+
+    while game.state() == GameState::Playing
+    {
+        let played = match puzzle
+        {
+            None => number_turn(game, guesser, out),
+            Some(_) => answer_turn(game, guesser, out),
+        };
+        if !played
+        {
+            game.give_up();
+            out.say(&format!("{} gave up, the {what} was {}.", guesser.name(), game.secret_text()));
+        }
+    }
+
+    let result = game.result();
+    if result.won
+    {
+        out.say(&format!("You needed {} attempts, your score: {}", result.attempts, result.score));
+    }
+    else if game.state() == GameState::Lost && config.max_attempts.is_some_and(|max| result.attempts >= max)
+    {
+        out.say(&format!("Out of attempts! The {what} was {}.", result.secret));
+    }
+
+    result
+}
+
+// One guess of the classic game, false when the guesser gives up
+fn number_turn(game: &mut Game, guesser: &mut dyn Guesser, out: &mut dyn OutputSink) -> bool
+{
+    let config = game.config().clone();
+    let Some(guess) = guesser.guess(&config, out) else {
+        return false;
+    };
+
+    // people see their own typing, bots' guesses need to be shown
+    if !guesser.is_human()
+    {
+        out.say(&format!("{} guesses {guess}", guesser.name()));
+    }
+
+    // A match expression is made up of arms. An arm consists of a
+    // pattern to match against, and the code that should be run if the
+    // value given to match fits that arm’s pattern
+    match game.guess(guess)
+    {
+        Ok(Ordering::Equal) => {
+            guesser.feedback(guess, Ordering::Equal);
+            out.say("You've guessed right!");
+        }
+        Ok(ordering) => {
+            guesser.feedback(guess, ordering);
+            out.say(if ordering == Ordering::Less { "Too small" } else { "Too big" });
+            out.say("---------------------");
+        }
+        Err(GuessError::OutOfRange) => {
+            out.say(&format!("The number is between {} and {}!", config.min, config.max));
+        }
+        Err(GuessError::GameOver) => {}
+    }
+    true
+}
+
+// One guess at a variant, false when the guesser gives up
+fn answer_turn(game: &mut Game, guesser: &mut dyn Guesser, out: &mut dyn OutputSink) -> bool
+{
+    let Some(line) = guesser.answer(out) else {
+        return false;
+    };
+
+    match game.answer(&line)
+    {
+        Ok((feedback, true)) => {
+            out.say(&feedback);
+            out.say("You've guessed right!");
+        }
+        Ok((feedback, false)) => {
+            out.say(&feedback);
+            out.say("---------------------");
+        }
+        Err(message) => out.say(&message),
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::guessing_game::HumanGuesser;
+
+    fn config(min: u32, max: u32, max_attempts: Option<u32>) -> GameConfig
+    {
+        GameConfig { min, max, max_attempts, ..GameConfig::default() }
+    }
+
+    fn play_script(config: &GameConfig, secret: u32, lines: &[&str]) -> (GameResult, Vec<String>)
+    {
+        let mut game = Game::new(config, secret);
+        let mut human = HumanGuesser::new(ScriptedInput::new(lines.iter().copied()));
+        let mut out = CapturedOutput::default();
+        let result = run(&mut game, &mut human, &mut out);
+        (result, out.lines)
+    }
+
+    #[test]
+    fn scripted_player_wins()
+    {
+        let (result, lines) = play_script(&config(1, 50, None), 13, &["25", "abc", "99", "12", "13"]);
+
+        assert!(result.won);
+        // the typo and the out of range number don't count
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.score, 6 * 100 / 3);
+        assert!(lines.contains(&String::from("Type a number!")));
+        assert!(lines.contains(&String::from("The number is between 1 and 50!")));
+        assert_eq!(lines.last().unwrap(), "You needed 3 attempts, your score: 200");
+    }
+
+    #[test]
+    fn unreadable_input_ends_it_instead_of_panicking()
+    {
+        let mut input: &[u8] = b"12\n\xff\xfe\n13\n";
+        assert_eq!(read_line_from(&mut input), Some(String::from("12\n")));
+        assert_eq!(read_line_from(&mut input), None);
+
+        let mut closed: &[u8] = b"";
+        assert_eq!(read_line_from(&mut closed), None);
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses()
+    {
+        let (result, lines) = play_script(&config(1, 100, Some(2)), 40, &["50", "25", "40"]);
+
+        assert!(!result.won);
+        assert_eq!(result.attempts, 2);
+        assert_eq!(result.score, 0);
+        assert_eq!(lines.last().unwrap(), "Out of attempts! The number was 40.");
+    }
+
+    #[test]
+    fn end_of_input_gives_up()
+    {
+        let (result, lines) = play_script(&config(1, 10, None), 7, &["3"]);
+
+        assert!(!result.won);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(lines.last().unwrap(), "human gave up, the number was 7.");
+    }
+
+    #[test]
+    fn finished_game_rejects_guesses()
+    {
+        let mut game = Game::new(&config(1, 10, None), 4);
+
+        assert_eq!(game.guess(0), Err(GuessError::OutOfRange));
+        assert_eq!(game.guess(2), Ok(Ordering::Less));
+        assert_eq!(game.guess(4), Ok(Ordering::Equal));
+        assert_eq!(game.state(), GameState::Won);
+        assert_eq!(game.guess(4), Err(GuessError::GameOver));
+        let moves: Vec<(u32, Ordering)> = game.history().iter().map(|m| (m.guess, m.ordering)).collect();
+        assert_eq!(moves, [(2, Ordering::Less), (4, Ordering::Equal)]);
+    }
+}
@@ -0,0 +1,354 @@
+use std::cmp::Ordering;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::engine::{InputSource, OutputSink, StdinInput};
+use super::GameConfig;
+
+// Anything that can play the guessing game: a person at the keyboard or one
+// of the bots below. The game loop asks for a guess, compares it with the
+// secret and reports the Ordering back.
+pub trait Guesser
+{
+    fn name(&self) -> &str;
+
+    fn is_human(&self) -> bool
+    {
+        false
+    }
+
+    // called before every game, bots reset what they learned here
+    fn start(&mut self, _config: &GameConfig) {}
+
+    // None means the guesser gives up. Anything the guesser wants to tell
+    // the player, like a prompt, goes to `out`.
+    fn guess(&mut self, config: &GameConfig, out: &mut dyn OutputSink) -> Option<u32>;
+
+    // The answer to a variant, as typed. Only people play those, so the
+    // bots give up right away.
+    fn answer(&mut self, _out: &mut dyn OutputSink) -> Option<String>
+    {
+        None
+    }
+
+    // `ordering` is guess.cmp(&secret_number), Less means the guess was
+    // too small
+    fn feedback(&mut self, _guess: u32, _ordering: Ordering) {}
+}
+
+// A person answering through an InputSource, stdin when playing for real
+pub struct HumanGuesser
+{
+    input: Box<dyn InputSource>,
+}
+
+impl HumanGuesser
+{
+    pub fn new(input: impl InputSource + 'static) -> Self
+    {
+        HumanGuesser { input: Box::new(input) }
+    }
+
+    pub fn stdin() -> Self
+    {
+        HumanGuesser::new(StdinInput)
+    }
+}
+
+impl Guesser for HumanGuesser
+{
+    fn name(&self) -> &str
+    {
+        "human"
+    }
+
+    fn is_human(&self) -> bool
+    {
+        true
+    }
+
+    fn guess(&mut self, config: &GameConfig, out: &mut dyn OutputSink) -> Option<u32>
+    {
+        // loop until we get something we can use as a guess
+        loop {
+            // no more lines, there will be no more guesses so we give up
+            let guess = self.answer(out)?;
+
+            // see chapter_2_guessing_game for what trim, parse and the match
+            // do here
+            let guess: u32 = match guess.trim().parse()
+            {
+                Ok(num) => {
+                    num
+                },
+                Err(_) => {
+                    out.say("Type a number!");
+                    continue;
+                }
+            };
+
+            // guesses outside of the range can't be right, they don't cost
+            // an attempt
+            if guess < config.min || guess > config.max
+            {
+                out.say(&format!("The number is between {} and {}!", config.min, config.max));
+                continue;
+            }
+
+            return Some(guess);
+        }
+    }
+
+    fn answer(&mut self, out: &mut dyn OutputSink) -> Option<String>
+    {
+        out.say("Please input your guess.");
+        self.input.read_line()
+    }
+}
+
+// The range of numbers still consistent with the feedback so far, shared
+// by all the bots
+#[derive(Debug, Clone, Copy)]
+struct Bounds
+{
+    low: u32,
+    high: u32,
+}
+
+impl Bounds
+{
+    fn new(config: &GameConfig) -> Self
+    {
+        Bounds { low: config.min, high: config.max }
+    }
+
+    // Feedback contradicting what came before (from a buggy or lying game)
+    // would leave low above high. The newest feedback wins then, so there is
+    // always a number left to guess.
+    fn narrow(&mut self, guess: u32, ordering: Ordering)
+    {
+        match ordering
+        {
+            Ordering::Less => {
+                self.low = self.low.max(guess.saturating_add(1));
+                self.high = self.high.max(self.low);
+            }
+            Ordering::Greater => {
+                self.high = self.high.min(guess.saturating_sub(1));
+                self.low = self.low.min(self.high);
+            }
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+            }
+        }
+    }
+
+    fn middle(&self) -> u32
+    {
+        self.low + (self.high - self.low) / 2
+    }
+}
+
+// Always guesses the middle of what is left, never needs more than
+// GameConfig::par() guesses
+pub struct BinarySearchBot
+{
+    bounds: Bounds,
+}
+
+impl Default for BinarySearchBot
+{
+    fn default() -> Self
+    {
+        BinarySearchBot::new()
+    }
+}
+
+impl BinarySearchBot
+{
+    pub fn new() -> Self
+    {
+        BinarySearchBot { bounds: Bounds { low: 0, high: u32::MAX } }
+    }
+}
+
+impl Guesser for BinarySearchBot
+{
+    fn name(&self) -> &str
+    {
+        "binary"
+    }
+
+    fn start(&mut self, config: &GameConfig)
+    {
+        self.bounds = Bounds::new(config);
+    }
+
+    fn guess(&mut self, _config: &GameConfig, _out: &mut dyn OutputSink) -> Option<u32>
+    {
+        Some(self.bounds.middle())
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering)
+    {
+        self.bounds.narrow(guess, ordering);
+    }
+}
+
+// Guesses uniformly at random, but only numbers that can still be right
+pub struct RandomBot
+{
+    bounds: Bounds,
+    rng: StdRng,
+}
+
+impl RandomBot
+{
+    pub fn new(seed: u64) -> Self
+    {
+        RandomBot { bounds: Bounds { low: 0, high: u32::MAX }, rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Guesser for RandomBot
+{
+    fn name(&self) -> &str
+    {
+        "random"
+    }
+
+    fn start(&mut self, config: &GameConfig)
+    {
+        self.bounds = Bounds::new(config);
+    }
+
+    fn guess(&mut self, _config: &GameConfig, _out: &mut dyn OutputSink) -> Option<u32>
+    {
+        Some(self.rng.gen_range(self.bounds.low..=self.bounds.high))
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering)
+    {
+        self.bounds.narrow(guess, ordering);
+    }
+}
+
+// Binary search with shaky hands: with probability `noise` the guess lands
+// anywhere in the remaining range instead of in its middle
+pub struct NoisyBot
+{
+    bounds: Bounds,
+    rng: StdRng,
+    noise: f64,
+}
+
+impl NoisyBot
+{
+    pub fn new(seed: u64, noise: f64) -> Self
+    {
+        NoisyBot {
+            bounds: Bounds { low: 0, high: u32::MAX },
+            rng: StdRng::seed_from_u64(seed),
+            noise: noise.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Guesser for NoisyBot
+{
+    fn name(&self) -> &str
+    {
+        "noisy"
+    }
+
+    fn start(&mut self, config: &GameConfig)
+    {
+        self.bounds = Bounds::new(config);
+    }
+
+    fn guess(&mut self, _config: &GameConfig, _out: &mut dyn OutputSink) -> Option<u32>
+    {
+        if self.rng.gen_bool(self.noise)
+        {
+            Some(self.rng.gen_range(self.bounds.low..=self.bounds.high))
+        }
+        else
+        {
+            Some(self.bounds.middle())
+        }
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering)
+    {
+        self.bounds.narrow(guess, ordering);
+    }
+}
+
+pub const BOT_NAMES: &[&str] = &["binary", "random", "noisy"];
+
+pub fn bot_by_name(name: &str, seed: u64) -> Option<Box<dyn Guesser>>
+{
+    match name
+    {
+        "binary" => Some(Box::new(BinarySearchBot::new())),
+        "random" => Some(Box::new(RandomBot::new(seed))),
+        "noisy" => Some(Box::new(NoisyBot::new(seed, 0.3))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::guessing_game::{play_with, Silent};
+
+    #[test]
+    fn binary_search_stays_within_par()
+    {
+        let mut rng = StdRng::seed_from_u64(34);
+        let mut bot = BinarySearchBot::new();
+
+        for game in 0..500
+        {
+            let min = rng.gen_range(0..1000);
+            let max = if game % 10 == 0 { u32::MAX } else { min + rng.gen_range(0..100_000) };
+            let config = GameConfig { min, max, max_attempts: None, seed: Some(game), ..GameConfig::default() };
+
+            let result = play_with(&config, &mut bot, &mut Silent);
+            assert!(result.won);
+            assert!(result.attempts <= config.par(), "{} attempts on {min}-{max}, par {}", result.attempts, config.par());
+        }
+    }
+
+    #[test]
+    fn contradicting_feedback_keeps_a_number_to_guess()
+    {
+        let config = GameConfig { min: 1, max: 100, ..GameConfig::default() };
+        let mut bots: Vec<Box<dyn Guesser>> = vec![Box::new(BinarySearchBot::new()), Box::new(RandomBot::new(1)), Box::new(NoisyBot::new(2, 1.0))];
+
+        for bot in &mut bots
+        {
+            bot.start(&config);
+            bot.feedback(50, Ordering::Less);
+            // 50 was too small, yet 30 is too big
+            bot.feedback(30, Ordering::Greater);
+            bot.feedback(u32::MAX, Ordering::Less);
+            bot.feedback(0, Ordering::Greater);
+            for _ in 0..10
+            {
+                assert!(bot.guess(&config, &mut Silent).is_some());
+            }
+        }
+
+        let mut bounds = Bounds::new(&config);
+        bounds.narrow(50, Ordering::Less);
+        bounds.narrow(30, Ordering::Greater);
+        assert_eq!((bounds.low, bounds.high), (29, 29));
+    }
+}
@@ -0,0 +1,734 @@
+// Multiplayer guessing game over TCP.
+//
+// The server picks the secret number, players connect, take turns and get
+// the same Less/Greater/Equal feedback as in the single player game. The
+// protocol is line based text, so `nc 127.0.0.1 7878` is a usable client:
+//
+//   client -> server            server -> client
+//   JOIN <name>                 WELCOME <name> <min> <max> <attempts|->
+//   GUESS <number>              JOINED <name> <players> <needed>
+//   QUIT                        START
+//                               TURN <name>
+//                               RESULT <name> <guess> LESS|GREATER|EQUAL
+//                               LEFT <name>
+//                               WINNER <name> <attempts>
+//                               NOWINNER <secret>
+//                               ERROR <message>
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use super::{bot_by_name, GameConfig, Guesser, HumanGuesser, Mode, StdoutSink, BOT_NAMES};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientMsg
+{
+    Join(String),
+    Guess(u32),
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerMsg
+{
+    Welcome { name: String, min: u32, max: u32, max_attempts: Option<u32> },
+    Joined { name: String, players: usize, needed: usize },
+    Start,
+    Turn(String),
+    Result { name: String, guess: u32, ordering: Ordering },
+    Left(String),
+    Winner { name: String, attempts: u32 },
+    NoWinner { secret: u32 },
+    Error(String),
+}
+
+pub fn ordering_word(ordering: Ordering) -> &'static str
+{
+    match ordering
+    {
+        Ordering::Less => "LESS",
+        Ordering::Greater => "GREATER",
+        Ordering::Equal => "EQUAL",
+    }
+}
+
+pub fn ordering_from_word(word: &str) -> Option<Ordering>
+{
+    match word
+    {
+        "LESS" => Some(Ordering::Less),
+        "GREATER" => Some(Ordering::Greater),
+        "EQUAL" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+impl fmt::Display for ClientMsg
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ClientMsg::Join(name) => write!(f, "JOIN {name}"),
+            ClientMsg::Guess(guess) => write!(f, "GUESS {guess}"),
+            ClientMsg::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for ClientMsg
+{
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err>
+    {
+        let (command, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let rest = rest.trim();
+
+        match command.to_uppercase().as_str()
+        {
+            // names are single words, so they can't break the protocol
+            "JOIN" if !rest.is_empty() && !rest.contains(char::is_whitespace) => {
+                Ok(ClientMsg::Join(rest.to_string()))
+            }
+            "JOIN" => Err(String::from("JOIN needs a name without spaces")),
+            "GUESS" => rest
+                .parse()
+                .map(ClientMsg::Guess)
+                .map_err(|_| format!("'{rest}' is not a number")),
+            "QUIT" => Ok(ClientMsg::Quit),
+            _ => Err(format!("unknown command '{command}'")),
+        }
+    }
+}
+
+impl fmt::Display for ServerMsg
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ServerMsg::Welcome { name, min, max, max_attempts } => {
+                let attempts = max_attempts.map_or(String::from("-"), |a| a.to_string());
+                write!(f, "WELCOME {name} {min} {max} {attempts}")
+            }
+            ServerMsg::Joined { name, players, needed } => write!(f, "JOINED {name} {players} {needed}"),
+            ServerMsg::Start => write!(f, "START"),
+            ServerMsg::Turn(name) => write!(f, "TURN {name}"),
+            ServerMsg::Result { name, guess, ordering } => {
+                write!(f, "RESULT {name} {guess} {}", ordering_word(*ordering))
+            }
+            ServerMsg::Left(name) => write!(f, "LEFT {name}"),
+            ServerMsg::Winner { name, attempts } => write!(f, "WINNER {name} {attempts}"),
+            ServerMsg::NoWinner { secret } => write!(f, "NOWINNER {secret}"),
+            ServerMsg::Error(message) => write!(f, "ERROR {message}"),
+        }
+    }
+}
+
+impl FromStr for ServerMsg
+{
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err>
+    {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |word: &str| word.parse::<u32>().map_err(|_| format!("bad number in '{line}'"));
+
+        match words[..]
+        {
+            ["WELCOME", name, min, max, attempts] => Ok(ServerMsg::Welcome {
+                name: name.to_string(),
+                min: number(min)?,
+                max: number(max)?,
+                max_attempts: if attempts == "-" { None } else { Some(number(attempts)?) },
+            }),
+            ["JOINED", name, players, needed] => Ok(ServerMsg::Joined {
+                name: name.to_string(),
+                players: number(players)? as usize,
+                needed: number(needed)? as usize,
+            }),
+            ["START"] => Ok(ServerMsg::Start),
+            ["TURN", name] => Ok(ServerMsg::Turn(name.to_string())),
+            ["RESULT", name, guess, ordering] => Ok(ServerMsg::Result {
+                name: name.to_string(),
+                guess: number(guess)?,
+                ordering: ordering_from_word(ordering).ok_or_else(|| format!("bad ordering in '{line}'"))?,
+            }),
+            ["LEFT", name] => Ok(ServerMsg::Left(name.to_string())),
+            ["WINNER", name, attempts] => Ok(ServerMsg::Winner { name: name.to_string(), attempts: number(attempts)? }),
+            ["NOWINNER", secret] => Ok(ServerMsg::NoWinner { secret: number(secret)? }),
+            ["ERROR", ..] => Ok(ServerMsg::Error(line.trim()["ERROR".len()..].trim().to_string())),
+            _ => Err(format!("unknown message '{line}'")),
+        }
+    }
+}
+
+// What connection threads tell the game thread
+enum Event
+{
+    Connected(usize, TcpStream),
+    Line(usize, String),
+    Disconnected(usize),
+}
+
+// The write half of a client. Writes happen on the connection's own thread,
+// so a client that stops reading can't stall the game loop, and one that
+// takes longer than WRITE_TIMEOUT to take a line is dropped.
+struct Connection
+{
+    id: usize,
+    outbox: mpsc::Sender<String>,
+    writer: thread::JoinHandle<()>,
+}
+
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl Connection
+{
+    fn open(id: usize, mut stream: TcpStream) -> Connection
+    {
+        let (outbox, lines) = mpsc::channel::<String>();
+        let writer = thread::spawn(move || {
+            let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+            for line in lines
+            {
+                if writeln!(stream, "{line}").is_err()
+                {
+                    break;
+                }
+            }
+            // all sent, or the client stopped reading; either way its reader
+            // thread sees the end and reports the disconnect
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+        Connection { id, outbox, writer }
+    }
+}
+
+struct Player
+{
+    id: usize,
+    name: String,
+    attempts: u32,
+}
+
+// The game thread decides what is sent, connection threads do the reading
+// and the writing
+struct Server
+{
+    config: GameConfig,
+    secret: u32,
+    needed: usize,
+    connections: Vec<Connection>,
+    // writer threads of dropped connections, still sending their last lines
+    closing: Vec<thread::JoinHandle<()>>,
+    // join order is turn order, the front player is the one to move
+    players: VecDeque<Player>,
+    started: bool,
+}
+
+impl Server
+{
+    fn send(&mut self, id: usize, msg: &ServerMsg)
+    {
+        if let Some(connection) = self.connections.iter().find(|c| c.id == id)
+        {
+            // a closed outbox means the writer gave up on the player, their
+            // reader thread reports the disconnect
+            let _ = connection.outbox.send(msg.to_string());
+        }
+    }
+
+    fn broadcast(&mut self, msg: &ServerMsg)
+    {
+        println!("{msg}");
+        let ids: Vec<usize> = self.players.iter().map(|p| p.id).collect();
+        for id in ids
+        {
+            self.send(id, msg);
+        }
+    }
+
+    fn drop_connection(&mut self, id: usize)
+    {
+        // closing the outbox ends the writer thread once it sent what's queued
+        if let Some(idx) = self.connections.iter().position(|c| c.id == id)
+        {
+            let Connection { writer, .. } = self.connections.remove(idx);
+            self.closing.push(writer);
+        }
+    }
+
+    fn current(&self) -> Option<usize>
+    {
+        self.players.front().map(|p| p.id)
+    }
+
+    // Moves the turn to the next player that still has attempts left and
+    // announces it, None when nobody can guess anymore
+    fn next_turn(&mut self) -> Option<()>
+    {
+        let exhausted = |p: &Player| self.config.max_attempts.is_some_and(|max| p.attempts >= max);
+        if self.players.iter().all(exhausted)
+        {
+            return None;
+        }
+
+        while self.players.front().is_some_and(exhausted)
+        {
+            self.players.rotate_left(1);
+        }
+
+        let name = self.players.front()?.name.clone();
+        self.broadcast(&ServerMsg::Turn(name));
+        Some(())
+    }
+
+    // Returns the winner's name and attempts once the game is over
+    fn handle(&mut self, event: Event) -> Option<Option<(String, u32)>>
+    {
+        match event
+        {
+            Event::Connected(id, stream) => {
+                self.connections.push(Connection::open(id, stream));
+                if self.started
+                {
+                    self.send(id, &ServerMsg::Error(String::from("game already started")));
+                    self.drop_connection(id);
+                }
+                None
+            }
+            Event::Disconnected(id) => self.leave(id),
+            Event::Line(id, line) => match line.parse::<ClientMsg>()
+            {
+                Ok(ClientMsg::Join(name)) => {
+                    self.join(id, name);
+                    None
+                }
+                Ok(ClientMsg::Guess(guess)) => self.guess(id, guess),
+                Ok(ClientMsg::Quit) => {
+                    self.drop_connection(id);
+                    self.leave(id)
+                }
+                Err(e) => {
+                    self.send(id, &ServerMsg::Error(e));
+                    None
+                }
+            },
+        }
+    }
+
+    fn join(&mut self, id: usize, name: String)
+    {
+        if self.started || self.players.iter().any(|p| p.id == id)
+        {
+            self.send(id, &ServerMsg::Error(String::from("already joined")));
+            return;
+        }
+
+        // two players with the same name would make TURN ambiguous, and a
+        // player may have picked a name like bob#2 themselves
+        let taken = |candidate: &str| self.players.iter().any(|p| p.name == candidate);
+        let mut unique = name.clone();
+        let mut n = 2;
+        while taken(&unique)
+        {
+            unique = format!("{name}#{n}");
+            n += 1;
+        }
+        let name = unique;
+
+        self.send(id, &ServerMsg::Welcome {
+            name: name.clone(),
+            min: self.config.min,
+            max: self.config.max,
+            max_attempts: self.config.max_attempts,
+        });
+        self.players.push_back(Player { id, name: name.clone(), attempts: 0 });
+        self.broadcast(&ServerMsg::Joined { name, players: self.players.len(), needed: self.needed });
+
+        if self.players.len() == self.needed
+        {
+            self.started = true;
+            self.broadcast(&ServerMsg::Start);
+            self.next_turn();
+        }
+    }
+
+    fn guess(&mut self, id: usize, guess: u32) -> Option<Option<(String, u32)>>
+    {
+        if !self.started
+        {
+            self.send(id, &ServerMsg::Error(String::from("game has not started")));
+            return None;
+        }
+        if self.current() != Some(id)
+        {
+            self.send(id, &ServerMsg::Error(String::from("not your turn")));
+            return None;
+        }
+        if guess < self.config.min || guess > self.config.max
+        {
+            let msg = format!("the number is between {} and {}", self.config.min, self.config.max);
+            self.send(id, &ServerMsg::Error(msg));
+            // still their turn, and a client only guesses after a TURN
+            let name = self.players.front()?.name.clone();
+            self.send(id, &ServerMsg::Turn(name));
+            return None;
+        }
+
+        let player = self.players.front_mut()?;
+        player.attempts += 1;
+        let (name, attempts) = (player.name.clone(), player.attempts);
+
+        // the same comparison as in the single player game
+        let ordering = guess.cmp(&self.secret);
+        self.broadcast(&ServerMsg::Result { name: name.clone(), guess, ordering });
+
+        if ordering == Ordering::Equal
+        {
+            self.broadcast(&ServerMsg::Winner { name: name.clone(), attempts });
+            return Some(Some((name, attempts)));
+        }
+
+        self.players.rotate_left(1);
+        match self.next_turn()
+        {
+            Some(()) => None,
+            None => {
+                self.broadcast(&ServerMsg::NoWinner { secret: self.secret });
+                Some(None)
+            }
+        }
+    }
+
+    fn leave(&mut self, id: usize) -> Option<Option<(String, u32)>>
+    {
+        self.drop_connection(id);
+
+        let idx = self.players.iter().position(|p| p.id == id)?;
+        let was_current = idx == 0;
+        let player = self.players.remove(idx)?;
+        self.broadcast(&ServerMsg::Left(player.name));
+
+        if !self.started
+        {
+            return None;
+        }
+
+        if self.players.is_empty()
+        {
+            return Some(None);
+        }
+
+        if was_current && self.next_turn().is_none()
+        {
+            self.broadcast(&ServerMsg::NoWinner { secret: self.secret });
+            return Some(None);
+        }
+
+        None
+    }
+}
+
+// Runs one game on `listener` once `players` players joined and returns
+// the winner with their number of attempts, None if nobody guessed it
+pub fn serve(listener: TcpListener, config: &GameConfig, players: usize) -> io::Result<Option<(String, u32)>>
+{
+    let (tx, rx) = mpsc::channel();
+    let addr = listener.local_addr()?;
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // accepting runs in its own thread, every connection gets a reader
+    // thread that turns its lines into events for the game loop below
+    let accepting = Arc::clone(&stop);
+    let acceptor = thread::spawn(move || {
+        for (id, stream) in listener.incoming().enumerate()
+        {
+            if accepting.load(AtomicOrdering::SeqCst)
+            {
+                return;
+            }
+            let Ok(stream) = stream else { continue };
+            let Ok(reader) = stream.try_clone() else { continue };
+            if tx.send(Event::Connected(id, stream)).is_err()
+            {
+                return;
+            }
+
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(reader).lines()
+                {
+                    let Ok(line) = line else { break };
+                    if tx.send(Event::Line(id, line)).is_err()
+                    {
+                        return;
+                    }
+                }
+                let _ = tx.send(Event::Disconnected(id));
+            });
+        }
+    });
+
+    let mut server = Server {
+        config: config.clone(),
+        secret: config.pick_secret(),
+        needed: players.max(1),
+        connections: Vec::new(),
+        closing: Vec::new(),
+        players: VecDeque::new(),
+        started: false,
+    };
+
+    let outcome = loop {
+        let Ok(event) = rx.recv() else {
+            break None;
+        };
+        if let Some(outcome) = server.handle(event)
+        {
+            break outcome;
+        }
+    };
+
+    let ids: Vec<usize> = server.connections.iter().map(|c| c.id).collect();
+    for id in ids
+    {
+        server.drop_connection(id);
+    }
+    // the last lines, WINNER or NOWINNER, still have to go out
+    for writer in server.closing.drain(..)
+    {
+        let _ = writer.join();
+    }
+
+    // accept() only returns for a connection, so one last one wakes the
+    // thread up to see it has to stop; the listener closes with it
+    stop.store(true, AtomicOrdering::SeqCst);
+    let _ = TcpStream::connect(addr);
+    let _ = acceptor.join();
+
+    Ok(outcome)
+}
+
+// Plays on a server with any Guesser. Every RESULT, also other players',
+// is passed to the guesser, so bots learn from everyone's guesses.
+pub fn play_remote(stream: TcpStream, name: &str, guesser: &mut dyn Guesser) -> io::Result<()>
+{
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "{}", ClientMsg::Join(name.to_string()))?;
+
+    let mut me = name.to_string();
+    let mut config = GameConfig::default();
+
+    for line in BufReader::new(stream).lines()
+    {
+        let line = line?;
+        let msg = match line.parse::<ServerMsg>()
+        {
+            Ok(msg) => msg,
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+
+        match msg
+        {
+            ServerMsg::Welcome { name, min, max, max_attempts } => {
+                println!("Joined as {name}, the number is between {min} and {max}.");
+                me = name;
+                config = GameConfig { min, max, max_attempts, ..GameConfig::default() };
+                guesser.start(&config);
+            }
+            ServerMsg::Joined { name, players, needed } => {
+                println!("{name} joined ({players}/{needed} players)");
+            }
+            ServerMsg::Start => println!("Guess the number!"),
+            ServerMsg::Turn(name) if name == me => {
+                match guesser.guess(&config, &mut StdoutSink)
+                {
+                    // the server would only ask again, and a bot would give
+                    // the same answer
+                    Some(guess) => {
+                        let guess = guess.clamp(config.min, config.max);
+                        if !guesser.is_human()
+                        {
+                            println!("{} guesses {guess}", guesser.name());
+                        }
+                        writeln!(writer, "{}", ClientMsg::Guess(guess))?;
+                    }
+                    None => {
+                        writeln!(writer, "{}", ClientMsg::Quit)?;
+                        return Ok(());
+                    }
+                }
+            }
+            ServerMsg::Turn(name) => println!("Waiting for {name}..."),
+            ServerMsg::Result { name, guess, ordering } => {
+                let verdict = match ordering
+                {
+                    Ordering::Less => "too small",
+                    Ordering::Greater => "too big",
+                    Ordering::Equal => "right!",
+                };
+                println!("{name} guessed {guess}: {verdict}");
+                guesser.feedback(guess, ordering);
+            }
+            ServerMsg::Left(name) => println!("{name} left the game"),
+            ServerMsg::Winner { name, attempts } => {
+                println!("{name} won after {attempts} attempts!");
+                return Ok(());
+            }
+            ServerMsg::NoWinner { secret } => {
+                println!("Nobody guessed it, the number was {secret}.");
+                return Ok(());
+            }
+            ServerMsg::Error(message) => println!("Server: {message}"),
+        }
+    }
+
+    Ok(())
+}
+
+pub fn serve_main(args: &[String]) -> ExitCode
+{
+    let mut port = DEFAULT_PORT;
+    let mut players = 2;
+    let mut game_args = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next()
+    {
+        let parsed = match arg.as_str()
+        {
+            "--port" => iter.next().and_then(|v| v.parse().ok()).map(|v| port = v),
+            "--players" => iter.next().and_then(|v| v.parse().ok()).map(|v| players = v),
+            // everything else configures the game, values included
+            _ => {
+                game_args.push(arg.clone());
+                continue;
+            }
+        };
+
+        if parsed.is_none()
+        {
+            eprintln!("guessing game: option '{arg}' needs a number");
+            return ExitCode::from(2);
+        }
+    }
+
+    let config = match GameConfig::from_args(&game_args)
+    {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("guessing game: {err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    // the protocol only knows LESS, GREATER and EQUAL
+    if config.mode != Mode::Classic
+    {
+        eprintln!("guessing game: only classic can be played over the network");
+        return ExitCode::from(2);
+    }
+
+    // only reachable from this machine
+    let listener = match TcpListener::bind(("127.0.0.1", port))
+    {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("guessing game: cannot listen on port {port}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // with --port 0 the OS picks the port, this line tells clients which
+    match listener.local_addr()
+    {
+        Ok(addr) => println!("listening on {addr}, waiting for {players} players"),
+        Err(e) => eprintln!("guessing game: {e}"),
+    }
+
+    match serve(listener, &config, players)
+    {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("guessing game: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+pub fn join_main(args: &[String]) -> ExitCode
+{
+    let mut addr = format!("127.0.0.1:{DEFAULT_PORT}");
+    let mut name = std::env::var("USER").unwrap_or_else(|_| String::from("player"));
+    let mut bot = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next()
+    {
+        let Some(value) = iter.next() else {
+            eprintln!("guessing game: option '{arg}' needs a value");
+            return ExitCode::from(2);
+        };
+
+        match arg.as_str()
+        {
+            "--addr" => addr = value.clone(),
+            "--name" => name = value.clone(),
+            "--bot" => bot = Some(value.clone()),
+            _ => {
+                eprintln!("guessing game: unexpected argument '{arg}'");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let mut guesser: Box<dyn Guesser> = match bot
+    {
+        None => Box::new(HumanGuesser::stdin()),
+        Some(bot) => match bot_by_name(&bot, rand::random())
+        {
+            Some(bot) => bot,
+            None => {
+                eprintln!("guessing game: unknown bot '{bot}', expected one of {}", BOT_NAMES.join(", "));
+                return ExitCode::from(2);
+            }
+        },
+    };
+
+    // names travel as one word in the protocol
+    let name: String = name.split_whitespace().collect::<Vec<_>>().join("_");
+    // the server turns a JOIN without a name away, and the client would
+    // wait for a WELCOME forever
+    if name.is_empty()
+    {
+        eprintln!("guessing game: the name can't be empty");
+        return ExitCode::from(2);
+    }
+
+    let result = TcpStream::connect(&addr).and_then(|stream| play_remote(stream, &name, guesser.as_mut()));
+    match result
+    {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("guessing game: {addr}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// First line of every scoreboard file. The version lets a future format
+// change recognise (and convert) old files instead of misreading them.
+const HEADER: &str = "guessing-game-scores v1";
+
+pub const TOP: usize = 10;
+
+// One won game, stored as one tab separated line:
+// category, name, min, max, attempts, duration in seconds, score
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreEntry
+{
+    // "easy", "normal", "hard" or "custom" for hand-picked ranges
+    pub category: String,
+    pub name: String,
+    pub min: u32,
+    pub max: u32,
+    pub attempts: u32,
+    pub duration: Duration,
+    pub score: u32,
+}
+
+impl ScoreEntry
+{
+    fn to_line(&self) -> String
+    {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{:.3}\t{}",
+            clean_field(&self.category),
+            clean_field(&self.name),
+            self.min,
+            self.max,
+            self.attempts,
+            self.duration.as_secs_f64(),
+            self.score
+        )
+    }
+
+    fn from_line(line: &str) -> Option<ScoreEntry>
+    {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [category, name, min, max, attempts, duration, score] = fields[..] else {
+            return None;
+        };
+
+        // negative, NaN or too long for a Duration is just as malformed
+        let duration = Duration::try_from_secs_f64(duration.parse().ok()?).ok()?;
+
+        Some(ScoreEntry {
+            category: category.to_string(),
+            name: name.to_string(),
+            min: min.parse().ok()?,
+            max: max.parse().ok()?,
+            attempts: attempts.parse().ok()?,
+            duration,
+            score: score.parse().ok()?,
+        })
+    }
+
+    // better score first, ties go to fewer attempts and then the faster game
+    fn rank_key(&self) -> (std::cmp::Reverse<u32>, u32, Duration)
+    {
+        (std::cmp::Reverse(self.score), self.attempts, self.duration)
+    }
+}
+
+// Tabs and newlines would break the line format, names are free text
+pub fn clean_field(field: &str) -> String
+{
+    let cleaned: String = field
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+
+    match cleaned.trim()
+    {
+        "" => String::from("anonymous"),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Scoreboard
+{
+    entries: Vec<ScoreEntry>,
+}
+
+// The first line, when it isn't exactly HEADER. "guessing-game-scores v10"
+// is not ours either, it only starts like it.
+fn unknown_header(text: &str) -> Option<&str>
+{
+    text.lines().next().map(str::trim).filter(|&header| header != HEADER)
+}
+
+impl Scoreboard
+{
+    // Parsing never fails: lines that can't be read are skipped and
+    // described in the returned warnings
+    pub fn parse(text: &str) -> (Scoreboard, Vec<String>)
+    {
+        let mut board = Scoreboard::default();
+        let mut warnings = Vec::new();
+        if text.is_empty()
+        {
+            return (board, warnings);
+        }
+        if let Some(header) = unknown_header(text)
+        {
+            warnings.push(format!("unknown scoreboard format '{header}'"));
+            return (board, warnings);
+        }
+
+        for (idx, line) in text.lines().enumerate().skip(1)
+        {
+            if line.trim().is_empty()
+            {
+                continue;
+            }
+
+            match ScoreEntry::from_line(line)
+            {
+                Some(entry) => board.entries.push(entry),
+                None => warnings.push(format!("line {}: skipping malformed entry", idx + 1)),
+            }
+        }
+
+        (board, warnings)
+    }
+
+    // A missing file is an empty scoreboard. A file in an unknown format is
+    // moved aside to <file>.bak, so saving the next score doesn't destroy it.
+    pub fn load(path: &Path) -> (Scoreboard, Vec<String>)
+    {
+        let text = match fs::read(path)
+        {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return (Scoreboard::default(), Vec::new()),
+            Err(e) => return (Scoreboard::default(), vec![format!("{}: {e}", path.display())]),
+        };
+
+        let (board, mut warnings) = Scoreboard::parse(&text);
+
+        // the same test parse() makes, a file parse() throws away is moved
+        let unreadable = !text.trim().is_empty() && unknown_header(&text).is_some();
+        if unreadable
+        {
+            let backup = backup_path(path);
+            match fs::rename(path, &backup)
+            {
+                Ok(()) => warnings.push(format!("moved unreadable scoreboard to {}", backup.display())),
+                Err(e) => warnings.push(format!("could not move unreadable scoreboard aside: {e}")),
+            }
+        }
+
+        (board, warnings)
+    }
+
+    // Writes to a temporary file first and renames it over the old one, so
+    // a crash while saving can't leave a half written scoreboard behind
+    pub fn save(&self, path: &Path) -> io::Result<()>
+    {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        fs::write(&tmp, self.to_text())?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn to_text(&self) -> String
+    {
+        let mut text = format!("{HEADER}\n");
+        for entry in &self.entries
+        {
+            text.push_str(&entry.to_line());
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn record(&mut self, entry: ScoreEntry)
+    {
+        self.entries.push(entry);
+    }
+
+    pub fn categories(&self) -> Vec<&str>
+    {
+        let mut categories: Vec<&str> = self.entries.iter().map(|e| e.category.as_str()).collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    pub fn top(&self, category: &str, n: usize) -> Vec<&ScoreEntry>
+    {
+        let mut entries: Vec<&ScoreEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.category == category)
+            .collect();
+        entries.sort_by_key(|e| e.rank_key());
+        entries.truncate(n);
+        entries
+    }
+
+    // Top TOP entries of every category, as a printable table
+    pub fn render(&self) -> String
+    {
+        if self.entries.is_empty()
+        {
+            return String::from("No scores yet.\n");
+        }
+
+        let mut out = String::new();
+        for category in self.categories()
+        {
+            out.push_str(&format!("== {category} ==\n"));
+            out.push_str(&format!(
+                "{:>3}  {:<16} {:>13} {:>8} {:>9} {:>5}\n",
+                "#", "name", "range", "attempts", "time", "score"
+            ));
+
+            for (rank, e) in self.top(category, TOP).iter().enumerate()
+            {
+                out.push_str(&format!(
+                    "{:>3}  {:<16} {:>13} {:>8} {:>8.1}s {:>5}\n",
+                    rank + 1,
+                    e.name,
+                    format!("{}-{}", e.min, e.max),
+                    e.attempts,
+                    e.duration.as_secs_f64(),
+                    e.score
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn backup_path(path: &Path) -> PathBuf
+{
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    fn entry(category: &str, name: &str, score: u32) -> ScoreEntry
+    {
+        ScoreEntry {
+            category: category.to_string(),
+            name: name.to_string(),
+            min: 1,
+            max: 50,
+            attempts: 6,
+            duration: Duration::from_millis(12_345),
+            score,
+        }
+    }
+
+    #[test]
+    fn round_trip()
+    {
+        let mut board = Scoreboard::default();
+        board.record(entry("easy", "ferris", 120));
+        board.record(entry("hard", "tab\tand\nnewline", 80));
+
+        let (parsed, warnings) = Scoreboard::parse(&board.to_text());
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(parsed.top("easy", TOP), vec![&entry("easy", "ferris", 120)]);
+        // control characters can't break the line format
+        assert_eq!(parsed.top("hard", TOP)[0].name, "tab and newline");
+        assert_eq!(parsed.to_text(), board.to_text().replace("tab\tand\nnewline", "tab and newline"));
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped_with_warnings()
+    {
+        let good = entry("normal", "ok", 100).to_line();
+        let text = format!(
+            "{HEADER}\n{good}\nnot an entry\n\nnormal\tx\t1\t50\tsix\t1.0\t100\nnormal\tx\t1\t50\t6\t-1.0\t100\nnormal\tx\t1\t50\t6\tNaN\t100\n{good}\n"
+        );
+
+        let (board, warnings) = Scoreboard::parse(&text);
+        assert_eq!(board.top("normal", TOP).len(), 2);
+        assert_eq!(
+            warnings,
+            [
+                "line 3: skipping malformed entry",
+                "line 5: skipping malformed entry",
+                "line 6: skipping malformed entry",
+                "line 7: skipping malformed entry",
+            ]
+        );
+    }
+
+    #[test]
+    fn huge_duration_is_malformed_not_a_crash()
+    {
+        let text = format!("{HEADER}\nnormal\tx\t1\t50\t6\t1e30\t100\n");
+
+        let (board, warnings) = Scoreboard::parse(&text);
+        assert_eq!(board, Scoreboard::default());
+        assert_eq!(warnings, ["line 2: skipping malformed entry"]);
+    }
+
+    #[test]
+    fn unknown_header_is_moved_aside()
+    {
+        let dir = env::temp_dir().join(format!("scoreboard-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("scores.txt");
+        fs::write(&path, "some other game's scores\nalice 10\n").unwrap();
+
+        let (board, warnings) = Scoreboard::load(&path);
+        assert_eq!(board, Scoreboard::default());
+        assert_eq!(warnings[0], "unknown scoreboard format 'some other game's scores'");
+        assert!(warnings[1].starts_with("moved unreadable scoreboard to "));
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "some other game's scores\nalice 10\n");
+
+        // a missing file is just an empty board
+        assert_eq!(Scoreboard::load(&path), (Scoreboard::default(), Vec::new()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn newer_versions_are_moved_aside_too()
+    {
+        let dir = env::temp_dir().join(format!("scoreboard-versions-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        for header in ["guessing-game-scores v2", "guessing-game-scores v10"]
+        {
+            let path = dir.join("scores.txt");
+            let text = format!("{header}
+normal	x	1	50	6	1.0	100
+");
+            fs::write(&path, &text).unwrap();
+
+            let (board, warnings) = Scoreboard::load(&path);
+            assert_eq!(board, Scoreboard::default());
+            assert_eq!(warnings[0], format!("unknown scoreboard format '{header}'"));
+            assert!(!path.exists(), "{header}");
+            assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), text);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn top_is_capped_per_category()
+    {
+        let mut board = Scoreboard::default();
+        for score in 0..15
+        {
+            board.record(entry("easy", &format!("e{score}"), score * 10));
+        }
+        for score in 0..3
+        {
+            board.record(entry("hard", &format!("h{score}"), score));
+        }
+
+        let top = board.top("easy", TOP);
+        assert_eq!(top.len(), TOP);
+        assert_eq!(top[0].score, 140);
+        assert_eq!(top[TOP - 1].score, 50);
+        assert_eq!(board.top("hard", TOP).len(), 3);
+
+        let rendered = board.render();
+        // header and column names of both, 10 + 3 rows
+        assert_eq!(rendered.lines().count(), 2 + TOP + 2 + 3);
+        assert!(!rendered.contains("e4 "));
+    }
+}
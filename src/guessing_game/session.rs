@@ -0,0 +1,458 @@
+// Recorded games and their replay.
+//
+// A session is one classic game: who played, the secret and every guess
+// with its feedback and the time it was made. It is saved as text, one
+// tab separated record per line:
+//
+//   guessing-game-session v1
+//   player    kim
+//   started   1760781234          unix time in seconds
+//   range     1    50
+//   attempts  -                   or the limit
+//   seed      -                   or the seed that picked the secret
+//   secret    42
+//   guess     1.234    25    LESS
+//   guess     2.001    37    LESS
+//   guess     2.950    42    EQUAL
+//   end       2.950    won       or lost, gave-up
+//
+// Replaying checks the log first: feedback that doesn't fit the secret,
+// guesses outside of the range or a secret the seed can't have picked
+// mean someone edited the file.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::engine::{self, Game, GameState, Move, OutputSink, StdoutSink};
+use super::network::{ordering_from_word, ordering_word};
+use super::scoreboard::clean_field;
+use super::{play_game, GameConfig, GameResult, Guesser};
+
+const HEADER: &str = "guessing-game-session v1";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome
+{
+    Won,
+    // out of attempts
+    Lost,
+    GaveUp,
+}
+
+impl fmt::Display for Outcome
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let name = match self
+        {
+            Outcome::Won => "won",
+            Outcome::Lost => "lost",
+            Outcome::GaveUp => "gave-up",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Outcome
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s
+        {
+            "won" => Ok(Outcome::Won),
+            "lost" => Ok(Outcome::Lost),
+            "gave-up" => Ok(Outcome::GaveUp),
+            _ => Err(format!("unknown outcome '{s}'")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session
+{
+    pub player: String,
+    // unix time in seconds
+    pub started: u64,
+    pub min: u32,
+    pub max: u32,
+    pub max_attempts: Option<u32>,
+    pub seed: Option<u64>,
+    pub secret: u32,
+    pub moves: Vec<Move>,
+    pub end: Duration,
+    pub outcome: Outcome,
+}
+
+fn optional<T: fmt::Display>(value: Option<T>) -> String
+{
+    value.map_or(String::from("-"), |v| v.to_string())
+}
+
+fn seconds(at: Duration) -> String
+{
+    format!("{:.3}", at.as_secs_f64())
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String>
+{
+    // negative, NaN and too long for a Duration are all errors
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("'{value}' is not a time in seconds"))
+}
+
+impl Session
+{
+    pub fn from_game(game: &Game, player: &str, started: SystemTime) -> Session
+    {
+        let config = game.config();
+        let outcome = match game.state()
+        {
+            GameState::Won => Outcome::Won,
+            _ if config.max_attempts.is_some_and(|max| game.history().len() as u32 >= max) => Outcome::Lost,
+            _ => Outcome::GaveUp,
+        };
+
+        Session {
+            player: clean_field(player),
+            started: started.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+            min: config.min,
+            max: config.max,
+            max_attempts: config.max_attempts,
+            seed: config.seed,
+            secret: game.secret(),
+            moves: game.history().to_vec(),
+            end: game.elapsed(),
+            outcome,
+        }
+    }
+
+    pub fn config(&self) -> GameConfig
+    {
+        GameConfig {
+            min: self.min,
+            max: self.max,
+            max_attempts: self.max_attempts,
+            seed: self.seed,
+            ..GameConfig::default()
+        }
+    }
+
+    pub fn to_text(&self) -> String
+    {
+        let mut text = format!(
+            "{HEADER}\nplayer\t{}\nstarted\t{}\nrange\t{}\t{}\nattempts\t{}\nseed\t{}\nsecret\t{}\n",
+            self.player,
+            self.started,
+            self.min,
+            self.max,
+            optional(self.max_attempts),
+            optional(self.seed),
+            self.secret
+        );
+
+        for m in &self.moves
+        {
+            text.push_str(&format!("guess\t{}\t{}\t{}\n", seconds(m.at), m.guess, ordering_word(m.ordering)));
+        }
+        text.push_str(&format!("end\t{}\t{}\n", seconds(self.end), self.outcome));
+        text
+    }
+
+    // Unlike the scoreboard nothing is skipped, a log with a line missing
+    // can't be replayed faithfully
+    pub fn parse(text: &str) -> Result<Session, String>
+    {
+        let mut lines = text.lines().enumerate();
+        match lines.next()
+        {
+            Some((_, header)) if header.trim() == HEADER => (),
+            _ => return Err(String::from("not a guessing game session")),
+        }
+
+        let mut session = Session {
+            player: String::new(),
+            started: 0,
+            min: 0,
+            max: 0,
+            max_attempts: None,
+            seed: None,
+            secret: 0,
+            moves: Vec::new(),
+            end: Duration::ZERO,
+            outcome: Outcome::GaveUp,
+        };
+        let mut seen = Vec::new();
+
+        for (idx, line) in lines
+        {
+            if line.trim().is_empty()
+            {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            let parsed = session.parse_field(&fields);
+            parsed.map_err(|e| format!("line {}: {e}", idx + 1))?;
+            seen.push(fields[0]);
+        }
+
+        for key in ["player", "range", "secret", "end"]
+        {
+            if !seen.contains(&key)
+            {
+                return Err(format!("missing '{key}' line"));
+            }
+        }
+
+        Ok(session)
+    }
+
+    // Applies one line of the log, split at tabs
+    fn parse_field(&mut self, fields: &[&str]) -> Result<(), String>
+    {
+        fn number<T: FromStr>(value: &str) -> Result<T, String>
+        {
+            value.parse().map_err(|_| format!("'{value}' is not a number"))
+        }
+
+        match fields
+        {
+            ["player", name] => self.player = name.to_string(),
+            ["started", started] => self.started = number(started)?,
+            ["range", min, max] => (self.min, self.max) = (number(min)?, number(max)?),
+            ["attempts", "-"] => self.max_attempts = None,
+            ["attempts", limit] => self.max_attempts = Some(number(limit)?),
+            ["seed", "-"] => self.seed = None,
+            ["seed", seed] => self.seed = Some(number(seed)?),
+            ["secret", secret] => self.secret = number(secret)?,
+            ["guess", at, guess, ordering] => {
+                let ordering = ordering_from_word(ordering).ok_or_else(|| format!("unknown feedback '{ordering}'"))?;
+                self.moves.push(Move { guess: number(guess)?, ordering, at: parse_seconds(at)? });
+            }
+            ["end", at, outcome] => (self.end, self.outcome) = (parse_seconds(at)?, outcome.parse()?),
+            _ => return Err(String::from("unexpected line")),
+        }
+
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Session, String>
+    {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Session::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()>
+    {
+        fs::write(path, self.to_text())
+    }
+
+    // Everything that doesn't add up, empty for an honest log
+    pub fn validate(&self) -> Vec<String>
+    {
+        let mut problems = Vec::new();
+
+        if self.min > self.max
+        {
+            problems.push(format!("range {}-{} is empty", self.min, self.max));
+        }
+        if self.secret < self.min || self.secret > self.max
+        {
+            problems.push(format!("secret {} is outside of {}-{}", self.secret, self.min, self.max));
+        }
+
+        // seeded games always pick the same secret, so the seed vouches for it
+        if self.seed.is_some() && self.min <= self.max
+        {
+            let picked = self.config().pick_secret();
+            if picked != self.secret
+            {
+                problems.push(format!("secret {} doesn't match the seed, which picks {picked}", self.secret));
+            }
+        }
+
+        let mut last = Duration::ZERO;
+        for (idx, m) in self.moves.iter().enumerate()
+        {
+            let n = idx + 1;
+            if m.guess < self.min || m.guess > self.max
+            {
+                problems.push(format!("guess {n}: {} is outside of {}-{}", m.guess, self.min, self.max));
+            }
+
+            let expected = m.guess.cmp(&self.secret);
+            if m.ordering != expected
+            {
+                problems.push(format!(
+                    "guess {n}: {} was answered {} but is {} for secret {}",
+                    m.guess,
+                    ordering_word(m.ordering),
+                    ordering_word(expected),
+                    self.secret
+                ));
+            }
+
+            if m.at < last
+            {
+                problems.push(format!("guess {n}: made before the guess before it"));
+            }
+            last = m.at;
+
+            if m.ordering.is_eq() && n < self.moves.len()
+            {
+                problems.push(format!("guess {n}: the game was already won"));
+            }
+        }
+
+        let attempts = self.moves.len() as u32;
+        if self.max_attempts.is_some_and(|max| attempts > max)
+        {
+            problems.push(format!("{attempts} guesses with a limit of {}", optional(self.max_attempts)));
+        }
+        if self.end < last
+        {
+            problems.push(String::from("the game ended before its last guess"));
+        }
+
+        let outcome = match self.moves.last()
+        {
+            Some(m) if m.ordering.is_eq() => Outcome::Won,
+            _ if self.max_attempts.is_some_and(|max| attempts >= max) => Outcome::Lost,
+            _ => Outcome::GaveUp,
+        };
+        if outcome != self.outcome
+        {
+            problems.push(format!("recorded as {} but the guesses say {outcome}", self.outcome));
+        }
+
+        problems
+    }
+}
+
+// Plays one classic game like play_with and saves it to `path`
+pub fn record(config: &GameConfig, guesser: &mut dyn Guesser, player: &str, path: &Path) -> GameResult
+{
+    let started = SystemTime::now();
+    let game = play_game(config, guesser, &mut StdoutSink);
+
+    let session = Session::from_game(&game, player, started);
+    if let Err(e) = session.save(path)
+    {
+        eprintln!("guessing game: could not save the session to {}: {e}", path.display());
+    }
+
+    game.result()
+}
+
+// Slower than this and a replay takes longer than anyone would wait
+const MIN_SPEED: f64 = 0.001;
+
+// Makes the recorded guesses again, at the recorded pace divided by
+// `speed`. No speed means no waiting at all.
+struct ReplayGuesser<'a>
+{
+    session: &'a Session,
+    next: usize,
+    speed: Option<f64>,
+}
+
+impl Guesser for ReplayGuesser<'_>
+{
+    fn name(&self) -> &str
+    {
+        &self.session.player
+    }
+
+    fn guess(&mut self, _config: &GameConfig, _out: &mut dyn OutputSink) -> Option<u32>
+    {
+        let m = self.session.moves.get(self.next)?;
+        let previous = self.next.checked_sub(1).map_or(Duration::ZERO, |i| self.session.moves[i].at);
+        self.next += 1;
+
+        if let Some(speed) = self.speed
+        {
+            // a pause too long for a Duration is as good as forever
+            let pause = m.at.saturating_sub(previous).as_secs_f64() / speed;
+            thread::sleep(Duration::try_from_secs_f64(pause).unwrap_or(Duration::MAX));
+        }
+
+        Some(m.guess)
+    }
+}
+
+// Plays the session again through the engine, which gives the feedback
+// anew, so the output is the same as the original game's
+pub fn replay(session: &Session, speed: Option<f64>, out: &mut dyn OutputSink) -> GameResult
+{
+    let mut game = Game::new(&session.config(), session.secret);
+    let mut guesser = ReplayGuesser { session, next: 0, speed };
+    engine::run(&mut game, &mut guesser, out)
+}
+
+pub fn replay_main(args: &[String]) -> ExitCode
+{
+    let mut file = None;
+    let mut speed = Some(1.0);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next()
+    {
+        match arg.as_str()
+        {
+            "--instant" => speed = None,
+            "--speed" => match iter.next().and_then(|v| v.parse::<f64>().ok())
+            {
+                Some(v) if v.is_finite() && v >= MIN_SPEED => speed = Some(v),
+                _ => {
+                    eprintln!("guessing game: option '--speed' needs a number of at least {MIN_SPEED}");
+                    return ExitCode::from(2);
+                }
+            },
+            _ if file.is_none() && !arg.starts_with('-') => file = Some(arg),
+            _ => {
+                eprintln!("guessing game: unexpected argument '{arg}'");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    let Some(file) = file else {
+        eprintln!("guessing game: replay needs a session file");
+        return ExitCode::from(2);
+    };
+
+    let session = match Session::load(Path::new(file))
+    {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("guessing game: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let problems = session.validate();
+    if !problems.is_empty()
+    {
+        eprintln!("guessing game: {file} has been tampered with:");
+        for problem in problems
+        {
+            eprintln!("  {problem}");
+        }
+        return ExitCode::FAILURE;
+    }
+
+    println!("Replaying {}'s game", session.player);
+    replay(&session, speed, &mut StdoutSink);
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,112 @@
+use super::{play_with, GameConfig, Guesser, Silent};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BotReport
+{
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub total_attempts: u64,
+    pub worst_attempts: u32,
+    pub total_score: u64,
+}
+
+impl BotReport
+{
+    pub fn average_attempts(&self) -> f64
+    {
+        if self.games == 0 { 0.0 } else { self.total_attempts as f64 / self.games as f64 }
+    }
+
+    pub fn average_score(&self) -> f64
+    {
+        if self.games == 0 { 0.0 } else { self.total_score as f64 / self.games as f64 }
+    }
+}
+
+// Every bot plays the same `games` secrets: game i is seeded with seed + i,
+// so bots are compared on exactly the same numbers
+pub fn run(config: &GameConfig, bots: &mut [Box<dyn Guesser>], games: u32, seed: u64) -> Vec<BotReport>
+{
+    bots.iter_mut()
+        .map(|bot| {
+            let mut report = BotReport {
+                name: bot.name().to_string(),
+                games,
+                wins: 0,
+                total_attempts: 0,
+                worst_attempts: 0,
+                total_score: 0,
+            };
+
+            for game in 0..games
+            {
+                let config = GameConfig { seed: Some(seed.wrapping_add(u64::from(game))), ..config.clone() };
+                let result = play_with(&config, bot.as_mut(), &mut Silent);
+
+                if result.won
+                {
+                    report.wins += 1;
+                }
+                report.total_attempts += u64::from(result.attempts);
+                report.worst_attempts = report.worst_attempts.max(result.attempts);
+                report.total_score += u64::from(result.score);
+            }
+
+            report
+        })
+        .collect()
+}
+
+pub fn render(config: &GameConfig, reports: &[BotReport]) -> String
+{
+    let mut out = format!(
+        "Tournament on {}-{} (par {} guesses)\n{:<8} {:>7} {:>7} {:>13} {:>6} {:>10}\n",
+        config.min, config.max, config.par(), "bot", "games", "wins", "avg attempts", "worst", "avg score"
+    );
+
+    for r in reports
+    {
+        out.push_str(&format!(
+            "{:<8} {:>7} {:>7} {:>13.2} {:>6} {:>10.1}\n",
+            r.name,
+            r.games,
+            r.wins,
+            r.average_attempts(),
+            r.worst_attempts,
+            r.average_score()
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::guessing_game::{bot_by_name, BOT_NAMES};
+
+    fn tournament(seed: u64) -> Vec<BotReport>
+    {
+        let config = GameConfig { min: 1, max: 1000, ..GameConfig::default() };
+        let mut bots: Vec<Box<dyn Guesser>> = BOT_NAMES.iter().filter_map(|name| bot_by_name(name, seed)).collect();
+        run(&config, &mut bots, 50, seed)
+    }
+
+    #[test]
+    fn same_seed_same_reports()
+    {
+        let reports = tournament(7);
+        assert_eq!(reports.len(), BOT_NAMES.len());
+        assert_eq!(tournament(7), reports);
+        assert_ne!(tournament(8), reports);
+
+        for report in &reports
+        {
+            assert_eq!((report.games, report.wins), (50, 50), "{}", report.name);
+        }
+        // binary search never needs more than par
+        assert!(reports[0].worst_attempts <= 10);
+    }
+}
@@ -0,0 +1,391 @@
+// Other ways to play: the secret and the feedback change, the attempts,
+// scoring and scoreboard stay the same as in the classic game.
+//
+//   hotcold     a number again, but you only learn how far off you are
+//   mastermind  a code of coloured pegs (digits), you learn how many are
+//               right and in the right place (exact) or only the right
+//               colour (partial)
+//   grid        a cell on a square grid, you learn which way to go
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::engine::Puzzle;
+use super::{Difficulty, GameConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Mode
+{
+    // Less/Greater feedback, the one bots and the network game play
+    #[default]
+    Classic,
+    HotCold,
+    Mastermind,
+    Grid,
+}
+
+impl fmt::Display for Mode
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let name = match self
+        {
+            Mode::Classic => "classic",
+            Mode::HotCold => "hotcold",
+            Mode::Mastermind => "mastermind",
+            Mode::Grid => "grid",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Mode
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s.to_lowercase().as_str()
+        {
+            "classic" => Ok(Mode::Classic),
+            "hotcold" | "hot-cold" => Ok(Mode::HotCold),
+            "mastermind" => Ok(Mode::Mastermind),
+            "grid" => Ok(Mode::Grid),
+            _ => Err(format!("unknown mode '{s}', expected classic, hotcold, mastermind or grid")),
+        }
+    }
+}
+
+// (pegs, colours) of the Mastermind code, the range options don't apply
+pub fn mastermind_shape(difficulty: Difficulty) -> (u32, u32)
+{
+    match difficulty
+    {
+        Difficulty::Easy => (3, 4),
+        Difficulty::Normal => (4, 6),
+        Difficulty::Hard => (5, 8),
+    }
+}
+
+// The grid has about as many cells as the range has numbers
+pub fn grid_side(config: &GameConfig) -> u32
+{
+    let cells = f64::from(config.max - config.min) + 1.0;
+    (cells.sqrt().ceil() as u32).max(1)
+}
+
+#[derive(Debug)]
+struct HotCold
+{
+    min: u32,
+    max: u32,
+    secret: u32,
+}
+
+// How far off a guess is, relative to the size of the range
+pub fn hot_cold_band(distance: u32, size: u64) -> &'static str
+{
+    // every band is at least one number wide, also in tiny ranges. size is
+    // u64 as 0..=u32::MAX has one number more than u32 can count.
+    let within = |percent: u64| u64::from(distance) <= (size * percent / 100).max(1);
+
+    if distance == 0
+    {
+        "Spot on!"
+    }
+    else if within(2)
+    {
+        "Burning!"
+    }
+    else if within(5)
+    {
+        "Hot"
+    }
+    else if within(10)
+    {
+        "Warm"
+    }
+    else if within(25)
+    {
+        "Cool"
+    }
+    else
+    {
+        "Cold"
+    }
+}
+
+impl Puzzle for HotCold
+{
+    fn rules(&self) -> String
+    {
+        format!("The number is between {} and {}, I'll only tell you how close you are.", self.min, self.max)
+    }
+
+    fn check(&self, guess: &str) -> Result<(String, bool), String>
+    {
+        let guess: u32 = guess.trim().parse().map_err(|_| String::from("Type a number!"))?;
+        if guess < self.min || guess > self.max
+        {
+            return Err(format!("The number is between {} and {}!", self.min, self.max));
+        }
+
+        let band = hot_cold_band(guess.abs_diff(self.secret), u64::from(self.max - self.min) + 1);
+        Ok((band.to_string(), guess == self.secret))
+    }
+
+    fn secret(&self) -> String
+    {
+        self.secret.to_string()
+    }
+}
+
+#[derive(Debug)]
+struct Mastermind
+{
+    colours: u32,
+    code: Vec<u32>,
+}
+
+// (exact, partial): pegs of the right colour in the right place, and pegs
+// of a right colour in the wrong place. Every secret peg is matched once.
+pub fn mastermind_pegs(code: &[u32], guess: &[u32]) -> (u32, u32)
+{
+    let exact = code.iter().zip(guess).filter(|(c, g)| c == g).count() as u32;
+
+    let colours = code.iter().chain(guess).copied().max().unwrap_or(0);
+    let common: u32 = (1..=colours)
+        .map(|colour| {
+            let count = |pegs: &[u32]| pegs.iter().filter(|&&p| p == colour).count() as u32;
+            count(code).min(count(guess))
+        })
+        .sum();
+
+    (exact, common - exact)
+}
+
+impl Puzzle for Mastermind
+{
+    fn rules(&self) -> String
+    {
+        format!("Crack the code of {} pegs, every peg is a colour from 1 to {}.", self.code.len(), self.colours)
+    }
+
+    fn check(&self, guess: &str) -> Result<(String, bool), String>
+    {
+        let pegs: Option<Vec<u32>> = guess
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(10).filter(|d| (1..=self.colours).contains(d)))
+            .collect();
+
+        let pegs = match pegs
+        {
+            Some(pegs) if pegs.len() == self.code.len() => pegs,
+            _ => return Err(format!("Type {} digits from 1 to {}!", self.code.len(), self.colours)),
+        };
+
+        let (exact, partial) = mastermind_pegs(&self.code, &pegs);
+        Ok((format!("{exact} exact, {partial} partial"), exact as usize == self.code.len()))
+    }
+
+    fn secret(&self) -> String
+    {
+        self.code.iter().map(|peg| peg.to_string()).collect()
+    }
+}
+
+#[derive(Debug)]
+struct Grid
+{
+    side: u32,
+    x: u32,
+    y: u32,
+}
+
+// Which way the secret cell is, x grows to the east and y to the north
+pub fn grid_direction(x: Ordering, y: Ordering) -> &'static str
+{
+    // the orderings are secret.cmp(&guess), Greater means go further
+    match (y, x)
+    {
+        (Ordering::Equal, Ordering::Equal) => "Found it!",
+        (Ordering::Greater, Ordering::Equal) => "Go north",
+        (Ordering::Less, Ordering::Equal) => "Go south",
+        (Ordering::Equal, Ordering::Greater) => "Go east",
+        (Ordering::Equal, Ordering::Less) => "Go west",
+        (Ordering::Greater, Ordering::Greater) => "Go north-east",
+        (Ordering::Greater, Ordering::Less) => "Go north-west",
+        (Ordering::Less, Ordering::Greater) => "Go south-east",
+        (Ordering::Less, Ordering::Less) => "Go south-west",
+    }
+}
+
+impl Puzzle for Grid
+{
+    fn rules(&self) -> String
+    {
+        format!("Find the cell on a {0}x{0} grid, x and y go from 1 to {0}.", self.side)
+    }
+
+    fn check(&self, guess: &str) -> Result<(String, bool), String>
+    {
+        let numbers: Option<Vec<u32>> = guess
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse().ok())
+            .collect();
+
+        let Some([x, y]) = numbers.as_deref() else {
+            return Err(format!("Type x,y with both between 1 and {}!", self.side));
+        };
+        let (x, y) = (*x, *y);
+        if !(1..=self.side).contains(&x) || !(1..=self.side).contains(&y)
+        {
+            return Err(format!("Type x,y with both between 1 and {}!", self.side));
+        }
+
+        let direction = grid_direction(self.x.cmp(&x), self.y.cmp(&y));
+        Ok((direction.to_string(), (x, y) == (self.x, self.y)))
+    }
+
+    fn secret(&self) -> String
+    {
+        format!("{},{}", self.x, self.y)
+    }
+}
+
+// The secret of a variant, played by engine::run like the classic game
+pub(crate) fn puzzle(config: &GameConfig) -> Box<dyn Puzzle>
+{
+    // seeded games always get the same secret, like in the classic game
+    let mut rng = StdRng::seed_from_u64(config.seed.unwrap_or_else(rand::random));
+
+    match config.mode
+    {
+        Mode::Classic => unreachable!("the classic game is played by the engine"),
+        Mode::HotCold => Box::new(HotCold { min: config.min, max: config.max, secret: config.pick_secret() }),
+        Mode::Mastermind => {
+            let (pegs, colours) = mastermind_shape(config.difficulty);
+            Box::new(Mastermind { colours, code: (0..pegs).map(|_| rng.gen_range(1..=colours)).collect() })
+        }
+        Mode::Grid => {
+            let side = grid_side(config);
+            Box::new(Grid { side, x: rng.gen_range(1..=side), y: rng.gen_range(1..=side) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::guessing_game::engine::{CapturedOutput, ScriptedInput};
+    use crate::guessing_game::{play_with, BinarySearchBot, GameResult, HumanGuesser};
+
+    fn play_script(config: &GameConfig, lines: &[&str]) -> (GameResult, Vec<String>)
+    {
+        let mut human = HumanGuesser::new(ScriptedInput::new(lines.iter().copied()));
+        let mut out = CapturedOutput::default();
+        let result = play_with(config, &mut human, &mut out);
+        (result, out.lines)
+    }
+
+    #[test]
+    fn mastermind_counts_every_peg_once()
+    {
+        assert_eq!(mastermind_pegs(&[1, 2, 3, 4], &[1, 2, 3, 4]), (4, 0));
+        assert_eq!(mastermind_pegs(&[1, 2, 3, 4], &[4, 3, 2, 1]), (0, 4));
+        assert_eq!(mastermind_pegs(&[1, 1, 2, 2], &[1, 2, 1, 1]), (1, 2));
+        assert_eq!(mastermind_pegs(&[1, 2, 3, 4], &[5, 5, 5, 5]), (0, 0));
+    }
+
+    #[test]
+    fn hot_cold_bands_shrink_towards_the_secret()
+    {
+        let bands: Vec<&str> = [0, 1, 2, 5, 10, 25, 26].iter().map(|&d| hot_cold_band(d, 100)).collect();
+        assert_eq!(bands, ["Spot on!", "Burning!", "Burning!", "Hot", "Warm", "Cool", "Cold"]);
+        // tiny ranges still say more than just "Cold"
+        assert_eq!(hot_cold_band(1, 10), "Burning!");
+        // the whole u32 range, where the size doesn't fit in a u32
+        let size = u64::from(u32::MAX) + 1;
+        assert_eq!(hot_cold_band(u32::MAX / 50, size), "Burning!");
+        assert_eq!(hot_cold_band(u32::MAX, size), "Cold");
+    }
+
+    #[test]
+    fn hot_cold_game_over_the_whole_u32_range()
+    {
+        let config = GameConfig { mode: Mode::HotCold, min: 0, max: u32::MAX, seed: Some(2), ..GameConfig::default() };
+        let secret = puzzle(&config).secret();
+
+        let (result, lines) = play_script(&config, &["0", &secret]);
+
+        assert!(result.won);
+        assert_eq!(result.attempts, 2);
+        assert!(lines.contains(&String::from("Spot on!")));
+    }
+
+    #[test]
+    fn bots_give_up_on_variants()
+    {
+        let config = GameConfig { mode: Mode::Grid, seed: Some(5), ..GameConfig::default() };
+        let mut out = CapturedOutput::default();
+
+        let result = play_with(&config, &mut BinarySearchBot::default(), &mut out);
+
+        assert!(!result.won);
+        assert_eq!(result.attempts, 0);
+        assert_eq!(out.lines.last().unwrap(), &format!("binary gave up, the answer was {}.", result.secret));
+    }
+
+    #[test]
+    fn grid_points_towards_the_secret()
+    {
+        assert_eq!(grid_direction(Ordering::Greater, Ordering::Less), "Go south-east");
+        assert_eq!(grid_direction(Ordering::Equal, Ordering::Greater), "Go north");
+        assert_eq!(grid_direction(Ordering::Equal, Ordering::Equal), "Found it!");
+    }
+
+    #[test]
+    fn scripted_grid_game_is_scored_against_its_par()
+    {
+        let config = GameConfig { mode: Mode::Grid, seed: Some(5), ..GameConfig::default() };
+        let side = grid_side(&config);
+        let secret = match puzzle(&config).secret().split_once(',')
+        {
+            Some((x, y)) => (x.parse::<u32>().unwrap(), y.parse::<u32>().unwrap()),
+            None => panic!("grid secret should be x,y"),
+        };
+        let miss = if secret.0 == side { 1 } else { side };
+        let (far, right) = (format!("{miss},{}", secret.1), format!("{} {}", secret.0, secret.1));
+
+        let (result, lines) = play_script(&config, &["0,0", "nowhere", &far, &right]);
+
+        assert_eq!(side, 8);
+        assert!(result.won);
+        assert_eq!(result.attempts, 2);
+        assert_eq!(result.score, config.par() * 100 / 2);
+        assert!(lines.contains(&String::from("Type x,y with both between 1 and 8!")));
+    }
+
+    #[test]
+    fn mastermind_game_can_be_lost()
+    {
+        let config = GameConfig { mode: Mode::Mastermind, max_attempts: Some(2), seed: Some(1), ..GameConfig::default() };
+        let secret = puzzle(&config).secret();
+        let wrong = if secret == "1111" { "2222" } else { "1111" };
+
+        let (result, lines) = play_script(&config, &["12", wrong, wrong, &secret]);
+
+        assert!(!result.won);
+        assert_eq!(result.attempts, 2);
+        assert_eq!(result.secret, secret);
+        assert!(lines.contains(&String::from("Type 4 digits from 1 to 6!")));
+        assert_eq!(lines.last().unwrap(), &format!("Out of attempts! The answer was {secret}."));
+    }
+}
@@ -1,5 +1,67 @@
+#![allow(unused, non_snake_case)]
+
 use std::thread;
 
+use crate::checks::Check;
+use crate::geometry::Rectangle;
+
+// The shirt giveaway of chapter_13_closures, out here so the library can
+// use it as well
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ShirtColor
+{
+    Red,
+    Blue,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Inventory
+{
+    pub shirts: Vec<ShirtColor>,
+}
+
+impl Inventory
+{
+    pub fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor
+    {
+        //
+        // The unwrap_or_else method on Option<T> is defined by the standard library. It takes one argument: a CLOSURE WITHOUT ANY ARGUMENTS that RETURNS A VALUE T (the same type stored in the Some variant of the Option<T>, in this case ShirtColor)
+        //
+        // The closure CAPTURES AN IMMUTABLE REFERENCE to the self
+        user_preference.unwrap_or_else(|| self.most_stocked())
+
+        // MORE:
+        // --> Closures don’t usually require you to annotate the types of the parameters or the return value like fn functions do
+        //
+        // --> Closures are typically short and relevant only within a narrow context rather than in any arbitrary scenario. Within these limited contexts, the COMPILER CAN INFER THE TYPES OF THE PARAMETERS AND THE RETURN TYPE, similar to how it’s able to infer the types of most variables
+    }
+
+    // ties go to blue
+    pub fn most_stocked(&self) -> ShirtColor
+    {
+        let mut num_red = 0;
+        let mut num_blue = 0;
+
+        for color in &self.shirts
+        {
+            match color
+            {
+                ShirtColor::Red => num_red += 1,
+                ShirtColor::Blue => num_blue += 1,
+            }
+        }
+        if num_red > num_blue
+        {
+            ShirtColor::Red
+        }
+        else
+        {
+            ShirtColor::Blue
+        }
+    }
+}
+
 pub fn chapter_13_iterators()
 {
     // The iterator pattern allows you to perform some task on a sequence of items in turn. An iterator is responsible for the logic of iterating over each item and determining when the sequence has finished. 
@@ -32,6 +94,25 @@ pub fn chapter_13_iterators()
     // What is type Item?
     // implementing the Iterator trait requires that you also define an Item type, and this Item type is used in the RETURN TYPE OF THE NEXT METHOD. In other words, the Item type will be the type returned from the iterator.
 
+    // next, sum and map, see iterator_examples
+    let (total, v2) = iterator_examples();
+    println!("sum: {total}, mapped: {v2:?}");
+
+    // CLOSURE CAPTURING VARIABLES IN ITERATOR, see shoes_in_size, and
+    // bad_search and GOOOD_search after it
+    let shoes = vec![
+        Shoe { size: 10, style: String::from("sneaker") },
+        Shoe { size: 13, style: String::from("sandal") },
+        Shoe { size: 10, style: String::from("boot") },
+    ];
+    println!("in size 10: {:?}", shoes_in_size(shoes, 10));
+}
+
+// What the iterator of chapter_13_iterators gives, the asserts are part of
+// the lesson. Returns the sum and the mapped vector.
+#[allow(clippy::useless_vec)]
+fn iterator_examples() -> (i32, Vec<i32>)
+{
     let v1 = vec![1, 2, 3];
 
     let mut v1_iter = v1.iter();
@@ -69,47 +150,47 @@ pub fn chapter_13_iterators()
 
     assert_eq!(v2, vec![2, 3, 4]);
 
-    // CLOSURE CAPTURING VARIABLES IN ITERATOR
-    #[derive(PartialEq, Debug)]
-    struct Shoe {
-        size: u32,
-        style: String,
-    };
+    (total, v2)
+}
 
-    fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe>
-    {
-        // -- We use into_iter, because we want to get ownership of shoes that
-        // have right size and are in vector, 
-        // -- Shoe struct does not know how to copy itself, 
-        // -- Our function also returns Vector of Shoe, not of references
-        // to Shoe
-        shoes.into_iter().filter(|s| s.size == shoe_size).collect()
-    }
+#[derive(PartialEq, Debug)]
+struct Shoe {
+    size: u32,
+    style: String,
+}
+
+fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe>
+{
+    // -- We use into_iter, because we want to get ownership of shoes that
+    // have right size and are in vector, 
+    // -- Shoe struct does not know how to copy itself, 
+    // -- Our function also returns Vector of Shoe, not of references
+    // to Shoe
+    // -- the closure captures shoe_size from the environment
+    shoes.into_iter().filter(|s| s.size == shoe_size).collect()
+}
 
-    // Good example of how to use methods, iterators and just functional style
-    // of programming
-    fn bad_search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
-        let mut results = Vec::new();
+// Good example of how to use methods, iterators and just functional style
+// of programming
+fn bad_search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let mut results = Vec::new();
 
-        for line in contents.lines() {
-            if line.contains(query) {
-                results.push(line);
-            }
+    for line in contents.lines() {
+        if line.contains(query) {
+            results.push(line);
         }
-
-        results
     }
 
-
-    fn GOOOD_search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> 
-    {
-        contents     
-            .lines()
-            .filter(|line| line.contains(query))
-            .collect()
-    }
+    results
+}
 
 
+fn GOOOD_search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> 
+{
+    contents     
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
 }
 
 pub fn chapter_13_closures()
@@ -117,56 +198,8 @@ pub fn chapter_13_closures()
     // CLOSURES
     // Closures are anonymous functions you can save in a variable or pass as arguments to other functions
 
-    #[derive(Debug, PartialEq, Copy, Clone)]
-    enum ShirtColor {
-        Red,
-        Blue
-    }
-
-    struct Inventory
-    {
-        shirts: Vec<ShirtColor>
-    }
-
-    impl Inventory
-    {
-        fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor
-        {
-            // 
-            // The unwrap_or_else method on Option<T> is defined by the standard library. It takes one argument: a CLOSURE WITHOUT ANY ARGUMENTS that RETURNS A VALUE T (the same type stored in the Some variant of the Option<T>, in this case ShirtColor)
-            //
-            // The closure CAPTURES AN IMMUTABLE REFERENCE to the self
-            user_preference.unwrap_or_else(|| self.most_stocked())
-
-            // MORE:
-            // --> Closures don’t usually require you to annotate the types of the parameters or the return value like fn functions do
-            //
-            // --> Closures are typically short and relevant only within a narrow context rather than in any arbitrary scenario. Within these limited contexts, the COMPILER CAN INFER THE TYPES OF THE PARAMETERS AND THE RETURN TYPE, similar to how it’s able to infer the types of most variables
-        }
-
-        fn most_stocked(&self) -> ShirtColor
-        {
-            let mut num_red = 0;
-            let mut num_blue = 0;
-
-            for color in &self.shirts 
-            {
-                match color 
-                {
-                    ShirtColor::Red => num_red += 1,
-                    ShirtColor::Blue => num_blue += 1,
-                }
-            }
-            if num_red > num_blue 
-            {
-                ShirtColor::Red
-            } 
-            else 
-            {
-                ShirtColor::Blue
-            }
-        }
-    }
+    // ShirtColor and Inventory, whose giveaway uses a closure, are at the
+    // top of this file
 
     { // exmpl
         let store = Inventory {
@@ -285,30 +318,13 @@ pub fn chapter_13_closures()
     // 
     // Because all closures implement FnOnce (cause every closure can be called), unwrap_or_else accepts all three kinds of closures and is as flexible as it can be.
 
-    // FnMut example:
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32,
-        height: u32,
-    }
-
-    let mut list = [
-        Rectangle { width: 10, height: 1 },
-        Rectangle { width: 3, height: 5 },
-        Rectangle { width: 7, height: 12 },
-    ];
-
-    // The reason sort_by_key is defined to take an FnMut closure is that it CALLS CLOSURE MULTIPLE TIMES: once for each item in the slice. 
-    // The closure |r| r.width doesn’t capture, mutate, or move anything out from its environment, so it meets the trait bound requirements.
-    list.sort_by_key(|r| r.width);
-    println!("{list:#?}");
+    // FnMut example, see sort_by_width below this function:
+    let mut list = rectangles();
+    let sort_operations = sort_by_width(&mut list);
+    println!("{list:#?}, sorted in {sort_operations} calls of the closure");
 
 
-    let mut list = [
-        Rectangle { width: 10, height: 1 },
-        Rectangle { width: 3, height: 5 },
-        Rectangle { width: 7, height: 12 },
-    ];
+    let mut list = rectangles();
 
     let mut sort_operations: Vec<String> = vec![];
     let value = String::from("closure called");
@@ -324,4 +340,68 @@ pub fn chapter_13_closures()
     // });
     println!("{list:#?}");
 
-}
\ No newline at end of file
+}
+
+// The book's Rectangle { width, height } is geometry::Rectangle here, the
+// same one chapter 5 uses
+fn rectangles() -> [Rectangle; 3]
+{
+    [
+        Rectangle::new(10, 1),
+        Rectangle::new(3, 5),
+        Rectangle::new(7, 12),
+    ]
+}
+
+// Returns how many times the closure was called
+fn sort_by_width(list: &mut [Rectangle]) -> usize
+{
+    // The reason sort_by_key is defined to take an FnMut closure is that it CALLS CLOSURE MULTIPLE TIMES: once for each item in the slice. 
+    // The closure only MUTATES the captured counter and doesn't move anything out from its environment, so it meets the trait bound requirements.
+    let mut num_sort_operations = 0;
+    list.sort_by_key(|r| {
+        num_sort_operations += 1;
+        r.width
+    });
+    num_sort_operations
+}
+
+// ############################## CHECKS ##################################
+
+pub fn chapter_13_closures_checks() -> Vec<Check>
+{
+    let store = Inventory { shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue] };
+
+    let mut list = rectangles();
+    let sort_operations = sort_by_width(&mut list);
+    let widths: Vec<u32> = list.iter().map(|r| r.width).collect();
+
+    vec![
+        Check::new("preference wins", ShirtColor::Red, store.giveaway(Some(ShirtColor::Red))),
+        Check::new("most stocked otherwise", ShirtColor::Blue, store.giveaway(None)),
+        Check::new("sort_by_key with FnMut", vec![3, 7, 10], widths),
+        Check::new("FnMut closure counted its calls", true, sort_operations >= list.len()),
+    ]
+}
+
+pub fn chapter_13_iterators_checks() -> Vec<Check>
+{
+    // iterator_examples asserts what next() gives on the way
+    let (total, v2) = iterator_examples();
+
+    let shoes = vec![
+        Shoe { size: 10, style: String::from("sneaker") },
+        Shoe { size: 13, style: String::from("sandal") },
+        Shoe { size: 10, style: String::from("boot") },
+    ];
+    let in_my_size: Vec<String> = shoes_in_size(shoes, 10).into_iter().map(|s| s.style).collect();
+
+    let poem = "Rust:\nsafe, fast, productive.\nPick three.";
+
+    vec![
+        Check::new("sum consumes the iterator", 6, total),
+        Check::new("map is lazy until collect", vec![2, 3, 4], v2),
+        Check::new("filter with a captured size", vec!["sneaker", "boot"], in_my_size.iter().map(String::as_str).collect()),
+        Check::new("the loop and the iterator search agree", bad_search("duct", poem), GOOOD_search("duct", poem)),
+    ]
+}
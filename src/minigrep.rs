@@ -1,23 +1,514 @@
 use std::env;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
 
-const QUERY_IDX: usize = 1;
-const PATH_IDX: usize = 2;
+mod error;
+mod file_types;
+mod stats;
 
-pub fn grep_main()
+pub use error::GrepError;
+pub use file_types::FileTypes;
+pub use stats::SearchStats;
+
+const USAGE: &str = "Usage: minigrep [OPTIONS] QUERY [PATH]...\n       minigrep --files [OPTIONS] [PATH]...";
+
+const HELP: &str = "\
+Searches files for lines containing QUERY. Directories are searched
+recursively, without PATH the current directory is searched.
+
+Options:
+  -i, --ignore-case        match QUERY case insensitively (also IGNORE_CASE=1)
+  -t, --type TYPE          only search files of TYPE, may be repeated
+  -T, --type-not TYPE      skip files of TYPE, may be repeated
+      --type-add NAME:GLOB define a new file type or extend an existing one
+      --type-list          print all known file types and exit
+      --files              print the files that would be searched and exit
+      --vimgrep            print path:line:column:text for every match
+  -0, --null               terminate file names with NUL instead of ':'
+      --stats              print search statistics at the end
+  -h, --help               print this help and exit
+  -V, --version            print the version and exit
+      --                   end of options, what follows is QUERY and PATHs
+
+Exit status:
+  0 a line matched, 1 nothing matched, 2 invalid arguments,
+  3 unknown or invalid file type, 4 file could not be read";
+
+// args are the command line arguments after `grep`, i.e. without the
+// program name: first the query, then the files/directories to search
+pub fn grep_main(args: &[String]) -> ExitCode
+{
+    match Config::build(args).and_then(|config| {
+        if config.help || config.version
+        {
+            return Ok((config, SearchStats::default()));
+        }
+        let stats = run(&config)?;
+        Ok((config, stats))
+    })
+    {
+        Ok((config, _)) if config.help => {
+            println!("{USAGE}\n\n{HELP}");
+            ExitCode::SUCCESS
+        }
+        Ok((config, _)) if config.version => {
+            println!("minigrep {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok((config, stats)) => {
+            if config.stats
+            {
+                println!("\n{stats}");
+            }
+
+            // like grep, a file we couldn't read is an error even when
+            // other files matched
+            if stats.files_unreadable > 0
+            {
+                ExitCode::from(error::EXIT_IO)
+            }
+            // listing files or types isn't a search, there is nothing to miss
+            else if stats.lines_matched == 0 && !config.files && !config.type_list
+            {
+                ExitCode::from(error::EXIT_NO_MATCH)
+            }
+            else
+            {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(err) => {
+            eprintln!("minigrep: {err}");
+            if err.is_usage()
+            {
+                eprintln!("\n{USAGE}\n\nFor more information, try '--help'.");
+            }
+            err.exit_code()
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Config
+{
+    pub query: String,
+    pub paths: Vec<PathBuf>,
+    pub ignore_case: bool,
+    pub types: FileTypes,
+    pub type_list: bool,
+    pub stats: bool,
+    pub null: bool,
+    pub vimgrep: bool,
+    pub files: bool,
+    // -h or -V was given, nothing else is filled in then
+    pub help: bool,
+    pub version: bool,
+}
+
+impl Config
 {
-    // 1) Reading command line arguments
-    //      - We need to annotate args, since collect needs to know to which 
-    //      collection we want
-    //      - First arg is query, second is file path
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3
+    pub fn build(args: &[String]) -> Result<Config, GrepError>
     {
-        println!("Provide exactly two arguments");
+        let mut positional = Vec::new();
+        let mut ignore_case = env::var("IGNORE_CASE").is_ok();
+        let mut type_list = false;
+        let mut stats = false;
+        let mut null = false;
+        let mut vimgrep = false;
+        let mut files = false;
+        let mut help = false;
+        let mut version = false;
+
+        // type selections are resolved after the loop, so that --type-add
+        // may come after the -t that uses it
+        let mut type_adds = Vec::new();
+        let mut selected = Vec::new();
+        let mut negated = Vec::new();
+
+        // the first error waits for the end of the loop, -h or -V given
+        // anywhere as an option still win over it
+        let mut error = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next()
+        {
+            // long options also accept the --name=value form
+            let (flag, inline_value) = match arg.split_once('=')
+            {
+                Some((flag, value)) if arg.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+
+            // the value is taken as is, even when it looks like an option
+            let mut value = || {
+                let value = inline_value.clone().or_else(|| iter.next().cloned());
+                if value.is_none()
+                {
+                    error.get_or_insert(GrepError::MissingValue(flag.to_string()));
+                }
+                value
+            };
+
+            match flag
+            {
+                "-h" | "--help" => help = true,
+                "-V" | "--version" => version = true,
+                "--" => positional.extend(iter.by_ref().cloned()),
+                "-i" | "--ignore-case" => ignore_case = true,
+                "-t" | "--type" => selected.extend(value()),
+                "-T" | "--type-not" => negated.extend(value()),
+                "--type-add" => type_adds.extend(value()),
+                "--type-list" => type_list = true,
+                "--stats" => stats = true,
+                "-0" | "--null" => null = true,
+                "--vimgrep" => vimgrep = true,
+                "--files" => files = true,
+                _ if flag.starts_with('-') && flag.len() > 1 => {
+                    error.get_or_insert(GrepError::unknown_option(flag));
+                }
+                _ => positional.push(arg.clone()),
+            }
+        }
+
+        if help || version
+        {
+            return Ok(Config { help, version, ..Config::default() });
+        }
+        if let Some(error) = error
+        {
+            return Err(error);
+        }
+
+        let mut types = FileTypes::new();
+        for def in &type_adds
+        {
+            types.add_def(def)?;
+        }
+        for name in &selected
+        {
+            types.select(name)?;
+        }
+        for name in &negated
+        {
+            types.negate(name)?;
+        }
+
+        // --files takes no query, every positional argument is a path
+        let mut positional = positional.into_iter();
+        let query = if files { None } else { positional.next() };
+        let query = match query
+        {
+            Some(query) => query,
+            None if files || type_list => String::new(),
+            None => return Err(GrepError::MissingQuery),
+        };
+
+        // without paths we search the current directory, like grep -r
+        let mut paths: Vec<PathBuf> = positional.map(PathBuf::from).collect();
+        if paths.is_empty()
+        {
+            paths.push(PathBuf::from("."));
+        }
+
+        Ok(Config {
+            query,
+            paths,
+            ignore_case,
+            types,
+            type_list,
+            stats,
+            null,
+            vimgrep,
+            files,
+            help,
+            version,
+        })
+    }
+}
+
+pub fn run(config: &Config) -> Result<SearchStats, GrepError>
+{
+    let start = Instant::now();
+    let mut stats = SearchStats::default();
+    list_or_search(config, &mut stats);
+    // timed here, so --files and --type-list get their time too
+    stats.elapsed = start.elapsed();
+    Ok(stats)
+}
+
+fn list_or_search(config: &Config, stats: &mut SearchStats)
+{
+    if config.type_list
+    {
+        for (name, globs) in config.types.definitions()
+        {
+            println!("{name}: {}", globs.join(", "));
+        }
+        return;
+    }
+
+    let files = collect_files(config, stats);
+
+    if config.files
+    {
+        // one path per line, or NUL terminated for `xargs -0`
+        let terminator = if config.null { '\0' } else { '\n' };
+        for file in &files
+        {
+            print!("{}{terminator}", file.display());
+        }
         return;
     }
 
-    let query = &args[QUERY_IDX];
-    let file_path = &args[PATH_IDX];
+    // like grep, we only prefix lines with the file name when there is more
+    // than one file the line could have come from, --vimgrep always does
+    let with_names = config.vimgrep
+        || files.len() > 1
+        || config.paths.iter().any(|p| p.is_dir());
+
+    for file in &files
+    {
+        let bytes = match fs::read(file)
+        {
+            Ok(bytes) => bytes,
+            Err(source) => {
+                unreadable(GrepError::Io { path: file.clone(), source }, stats);
+                continue;
+            }
+        };
+        stats.files_scanned += 1;
+        stats.bytes_searched += bytes.len() as u64;
+
+        // files that aren't valid UTF-8 are treated as binary and skipped
+        let Ok(contents) = String::from_utf8(bytes) else {
+            continue;
+        };
+
+        if config.vimgrep
+        {
+            let mut lines_matched = 0;
+
+            // one output line per match, so a line matching twice is
+            // printed twice with different columns
+            for (line_no, line) in contents.lines().enumerate()
+            {
+                let columns = match_columns(&config.query, line, config.ignore_case);
+                if columns.is_empty()
+                {
+                    continue;
+                }
+
+                lines_matched += 1;
+                for col in columns
+                {
+                    println!("{}{}:{}:{line}", file_prefix(file, config.null), line_no + 1, col + 1);
+                }
+            }
+
+            if lines_matched > 0
+            {
+                stats.files_matched += 1;
+                stats.lines_matched += lines_matched;
+            }
+            continue;
+        }
+
+        let results = if config.ignore_case
+        {
+            search_case_insensitive(&config.query, &contents)
+        }
+        else
+        {
+            search(&config.query, &contents)
+        };
+
+        if !results.is_empty()
+        {
+            stats.files_matched += 1;
+            stats.lines_matched += results.len();
+        }
+
+        for line in results
+        {
+            if with_names
+            {
+                println!("{}{line}", file_prefix(file, config.null));
+            }
+            else
+            {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+// File name printed in front of a matching line, with --null the name is
+// terminated by NUL instead of ':' so names containing ':' stay unambiguous
+fn file_prefix(file: &Path, null: bool) -> String
+{
+    let separator = if null { '\0' } else { ':' };
+    format!("{}{separator}", file.display())
+}
+
+// A file or directory we couldn't read doesn't end the search, like grep
+// we say so and go on with the next one
+fn unreadable(err: GrepError, stats: &mut SearchStats)
+{
+    eprintln!("minigrep: {err}");
+    stats.files_unreadable += 1;
+}
+
+// Expands the configured paths into the list of files to search.
+// Files given explicitly are always searched, type filters only apply to
+// files found while walking directories. Directories that can't be read
+// are reported and counted in stats.
+pub fn collect_files(config: &Config, stats: &mut SearchStats) -> Vec<PathBuf>
+{
+    let mut files = Vec::new();
+
+    for path in &config.paths
+    {
+        if path.is_dir()
+        {
+            walk_dir(path, &config.types, &mut files, stats);
+        }
+        else
+        {
+            files.push(path.clone());
+        }
+    }
+
+    files
+}
+
+fn walk_dir(dir: &Path, types: &FileTypes, files: &mut Vec<PathBuf>, stats: &mut SearchStats)
+{
+    let entries = fs::read_dir(dir).and_then(|entries| entries.map(|entry| entry.map(|e| e.path())).collect::<Result<Vec<_>, _>>());
+    let mut entries = match entries
+    {
+        Ok(entries) => entries,
+        Err(source) => return unreadable(GrepError::Io { path: dir.to_path_buf(), source }, stats),
+    };
+    // read_dir gives no ordering guarantees, we sort so output is stable
+    entries.sort();
+
+    for path in entries
+    {
+        // hidden files and directories (.git, .cache, ...) are skipped
+        let is_hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if is_hidden
+        {
+            continue;
+        }
+
+        // symlink_metadata doesn't follow links, so a link to a directory
+        // (maybe one further up, which would never end) isn't walked into
+        let metadata = match fs::symlink_metadata(&path)
+        {
+            Ok(metadata) => metadata,
+            Err(source) => {
+                unreadable(GrepError::Io { path, source }, stats);
+                continue;
+            }
+        };
+
+        if metadata.is_dir()
+        {
+            walk_dir(&path, types, files, stats);
+        }
+        else if metadata.file_type().is_symlink() && path.is_dir()
+        {
+            continue;
+        }
+        else if types.is_empty() || types.matches(&path)
+        {
+            files.push(path);
+        }
+    }
+}
+
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str>
+{
+    contents
+        .lines()
+        .filter(|line| line.contains(query))
+        .collect()
+}
+
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str>
+{
+    let query = query.to_lowercase();
+
+    contents
+        .lines()
+        .filter(|line| line.to_lowercase().contains(&query))
+        .collect()
+}
+
+// Byte offsets of every non-overlapping match of query in line
+pub fn match_columns(query: &str, line: &str, ignore_case: bool) -> Vec<usize>
+{
+    // an empty query matches every line once, at its start
+    if query.is_empty()
+    {
+        return vec![0];
+    }
+
+    if !ignore_case
+    {
+        return line.match_indices(query).map(|(idx, _)| idx).collect();
+    }
+
+    // lowercasing can change the byte length of some characters, so instead
+    // of searching a lowercased copy we compare char by char from every
+    // char boundary of the original line
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let mut columns = Vec::new();
+    let mut skip_until = 0;
+
+    for (idx, _) in line.char_indices()
+    {
+        if idx < skip_until
+        {
+            continue;
+        }
+
+        if let Some(len) = match_len_ignore_case(&query, &line[idx..])
+        {
+            columns.push(idx);
+            skip_until = idx + len;
+        }
+    }
+
+    columns
+}
+
+// Length in bytes of the prefix of text matching the lowercased query
+fn match_len_ignore_case(query: &[char], text: &str) -> Option<usize>
+{
+    let mut matched = 0;
+
+    for (idx, c) in text.char_indices()
+    {
+        for lower in c.to_lowercase()
+        {
+            if query.get(matched) != Some(&lower)
+            {
+                return None;
+            }
+            matched += 1;
+        }
+
+        if matched == query.len()
+        {
+            return Some(idx + c.len_utf8());
+        }
+    }
 
-}
\ No newline at end of file
+    None
+}
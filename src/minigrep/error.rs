@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// Exit codes, 0 and 1 follow grep: 0 when something matched, 1 when nothing
+// did, every kind of error below gets its own code
+pub const EXIT_NO_MATCH: u8 = 1;
+pub const EXIT_USAGE: u8 = 2;
+pub const EXIT_FILE_TYPE: u8 = 3;
+pub const EXIT_IO: u8 = 4;
+
+// Long options we know about, used to suggest a fix for a misspelled one
+const LONG_OPTIONS: &[&str] = &[
+    "--ignore-case",
+    "--type",
+    "--type-not",
+    "--type-add",
+    "--type-list",
+    "--stats",
+    "--null",
+    "--vimgrep",
+    "--files",
+    "--help",
+    "--version",
+];
+
+#[derive(Debug)]
+pub enum GrepError
+{
+    MissingQuery,
+    MissingValue(String),
+    UnknownOption { option: String, suggestion: Option<&'static str> },
+    UnknownType(String),
+    InvalidTypeDef(String),
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl GrepError
+{
+    pub fn unknown_option(option: &str) -> GrepError
+    {
+        GrepError::UnknownOption {
+            option: option.to_string(),
+            suggestion: suggest_option(option),
+        }
+    }
+
+    // usage errors are the ones fixed by changing the command line, we
+    // print the usage line after them
+    pub fn is_usage(&self) -> bool
+    {
+        matches!(
+            self,
+            GrepError::MissingQuery | GrepError::MissingValue(_) | GrepError::UnknownOption { .. }
+        )
+    }
+
+    pub fn exit_code(&self) -> ExitCode
+    {
+        let code = match self
+        {
+            GrepError::MissingQuery
+            | GrepError::MissingValue(_)
+            | GrepError::UnknownOption { .. } => EXIT_USAGE,
+            GrepError::UnknownType(_) | GrepError::InvalidTypeDef(_) => EXIT_FILE_TYPE,
+            GrepError::Io { .. } => EXIT_IO,
+        };
+
+        ExitCode::from(code)
+    }
+}
+
+impl fmt::Display for GrepError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            GrepError::MissingQuery => write!(f, "no query given"),
+            GrepError::MissingValue(flag) => write!(f, "option '{flag}' needs a value"),
+            GrepError::UnknownOption { option, suggestion: Some(suggestion) } => {
+                write!(f, "unknown option '{option}', did you mean {suggestion}?")
+            }
+            GrepError::UnknownOption { option, suggestion: None } => {
+                write!(f, "unknown option '{option}'")
+            }
+            GrepError::UnknownType(name) => {
+                write!(f, "unknown file type '{name}', see --type-list")
+            }
+            GrepError::InvalidTypeDef(def) => {
+                write!(f, "invalid type definition '{def}', expected name:glob")
+            }
+            GrepError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+        }
+    }
+}
+
+impl Error for GrepError
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)>
+    {
+        match self
+        {
+            GrepError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// Closest known long option, if it is close enough to be a likely typo
+fn suggest_option(option: &str) -> Option<&'static str>
+{
+    // short options are too short for the edit distance to mean anything
+    if option.len() < 3
+    {
+        return None;
+    }
+
+    LONG_OPTIONS
+        .iter()
+        .map(|known| (edit_distance(option, known), *known))
+        .filter(|(distance, known)| *distance <= (known.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+// Levenshtein distance, computed row by row
+fn edit_distance(a: &str, b: &str) -> usize
+{
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate()
+    {
+        let mut row = vec![i + 1];
+
+        for (j, cb) in b.iter().enumerate()
+        {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            row.push(substitution.min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+
+        prev = row;
+    }
+
+    prev[b.len()]
+}
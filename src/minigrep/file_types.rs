@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::GrepError;
+
+// Built-in file types, each mapped to the globs that select it.
+// A glob is either "*.ext" (matched against the end of the file name) or an
+// exact file name like "Makefile".
+const DEFAULT_TYPES: &[(&str, &[&str])] = &[
+    ("c", &["*.c", "*.h"]),
+    ("cpp", &["*.cpp", "*.cc", "*.cxx", "*.hpp", "*.hh", "*.hxx"]),
+    ("csv", &["*.csv"]),
+    ("docker", &["Dockerfile", "*.dockerfile"]),
+    ("html", &["*.html", "*.htm"]),
+    ("js", &["*.js", "*.mjs", "*.cjs"]),
+    ("json", &["*.json"]),
+    ("log", &["*.log"]),
+    ("make", &["Makefile", "makefile", "GNUmakefile", "*.mk"]),
+    ("md", &["*.md", "*.markdown"]),
+    ("py", &["*.py", "*.pyi"]),
+    ("rust", &["*.rs"]),
+    ("sh", &["*.sh", "*.bash", "*.zsh"]),
+    ("toml", &["*.toml", "Cargo.lock"]),
+    ("txt", &["*.txt"]),
+    ("yaml", &["*.yaml", "*.yml"]),
+];
+
+pub struct FileTypes
+{
+    // BTreeMap so that --type-list is printed in alphabetical order
+    defs: BTreeMap<String, Vec<String>>,
+    selected: Vec<String>,
+    negated: Vec<String>,
+}
+
+impl Default for FileTypes
+{
+    fn default() -> Self
+    {
+        FileTypes::new()
+    }
+}
+
+impl FileTypes
+{
+    pub fn new() -> Self
+    {
+        let defs = DEFAULT_TYPES
+            .iter()
+            .map(|(name, globs)| {
+                (name.to_string(), globs.iter().map(|g| g.to_string()).collect())
+            })
+            .collect();
+
+        FileTypes { defs, selected: Vec::new(), negated: Vec::new() }
+    }
+
+    // Adds a definition in the "name:glob" form used by --type-add.
+    // Adding to an existing type extends it instead of replacing it.
+    pub fn add_def(&mut self, def: &str) -> Result<(), GrepError>
+    {
+        let Some((name, glob)) = def.split_once(':') else {
+            return Err(GrepError::InvalidTypeDef(def.to_string()));
+        };
+
+        let (name, glob) = (name.trim(), glob.trim());
+        if name.is_empty() || glob.is_empty()
+        {
+            return Err(GrepError::InvalidTypeDef(def.to_string()));
+        }
+
+        self.defs.entry(name.to_string()).or_default().push(glob.to_string());
+        Ok(())
+    }
+
+    // -t: only files of the selected types are searched
+    pub fn select(&mut self, name: &str) -> Result<(), GrepError>
+    {
+        self.check_known(name)?;
+        self.selected.push(name.to_string());
+        Ok(())
+    }
+
+    // -T: files of the negated types are skipped
+    pub fn negate(&mut self, name: &str) -> Result<(), GrepError>
+    {
+        self.check_known(name)?;
+        self.negated.push(name.to_string());
+        Ok(())
+    }
+
+    fn check_known(&self, name: &str) -> Result<(), GrepError>
+    {
+        if self.defs.contains_key(name)
+        {
+            Ok(())
+        }
+        else
+        {
+            Err(GrepError::UnknownType(name.to_string()))
+        }
+    }
+
+    // true when no -t/-T was given, so callers can skip the per-file check
+    pub fn is_empty(&self) -> bool
+    {
+        self.selected.is_empty() && self.negated.is_empty()
+    }
+
+    pub fn matches(&self, path: &Path) -> bool
+    {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+
+        let is_type = |name: &String| {
+            self.defs[name].iter().any(|glob| glob_matches(glob, file_name))
+        };
+
+        if self.negated.iter().any(is_type)
+        {
+            return false;
+        }
+
+        self.selected.is_empty() || self.selected.iter().any(is_type)
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = (&str, &[String])>
+    {
+        self.defs.iter().map(|(name, globs)| (name.as_str(), globs.as_slice()))
+    }
+}
+
+fn glob_matches(glob: &str, file_name: &str) -> bool
+{
+    match glob.strip_prefix('*')
+    {
+        Some(suffix) => file_name.len() > suffix.len() && file_name.ends_with(suffix),
+        None => file_name == glob,
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+use std::time::Duration;
+
+// Counters collected while searching, returned by run() and printed by --stats
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SearchStats
+{
+    pub files_scanned: usize,
+    pub files_matched: usize,
+    pub bytes_searched: u64,
+    pub lines_matched: usize,
+    // files and directories that couldn't be read, each one is reported on
+    // stderr when it's found and the search goes on without it
+    pub files_unreadable: usize,
+    pub elapsed: Duration,
+}
+
+impl SearchStats
+{
+    // bytes per second, 0 when the run was too fast to measure
+    pub fn throughput(&self) -> f64
+    {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0
+        {
+            self.bytes_searched as f64 / secs
+        }
+        else
+        {
+            0.0
+        }
+    }
+}
+
+impl fmt::Display for SearchStats
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        const MIB: f64 = 1024.0 * 1024.0;
+
+        writeln!(f, "{} files scanned", self.files_scanned)?;
+        writeln!(f, "{} files contained matches", self.files_matched)?;
+        writeln!(f, "{} bytes searched", self.bytes_searched)?;
+        writeln!(f, "{} matched lines", self.lines_matched)?;
+        if self.files_unreadable > 0
+        {
+            writeln!(f, "{} files could not be read", self.files_unreadable)?;
+        }
+        writeln!(f, "{:.6} seconds elapsed", self.elapsed.as_secs_f64())?;
+        write!(f, "{:.2} MiB/s throughput", self.throughput() / MIB)
+    }
+}
@@ -1,5 +1,101 @@
+#![allow(unused, non_snake_case)]
+
 use std::ops::Deref;
 
+use crate::checks::Check;
+
+// MyBox of chapter_15_deref and the cons list of chapter_15_Box_pointer,
+// out here so the library can use them as well
+
+// Box<T> type is defined as tuple struct with one elem
+// Syntax of tuple struct: struct Name<T, U> (T, U, T, ....)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MyBox<T>(T);
+
+impl<T> MyBox<T>
+{
+    pub fn new(x: T) -> MyBox<T>
+    {
+        MyBox(x)
+    }
+}
+
+// Now we need to implement deref trait
+impl<T> Deref for MyBox<T>
+{
+    type Target = T; // associated type, what deref will return
+
+    fn deref(&self) -> &Self::Target
+    {
+        // we return reference to the value stored in MyBox, so that we can
+        // use deref '*' operator
+        &self.0
+    }
+}
+
+// Box<T> is a pointer, so it's size is always the same and known, which
+// breaks the recursion (see chapter_15_Box_pointer)
+#[derive(Debug, Clone, PartialEq)]
+pub enum List
+{
+    Cons(i32, Box<List>), // tuple of int value and pointer to another List
+    Nil,
+}
+
+impl List
+{
+    // Cons(1, Cons(2, Nil)) from [1, 2]
+    pub fn from_slice(values: &[i32]) -> List
+    {
+        values
+            .iter()
+            .rev()
+            .fold(List::Nil, |tail, &value| List::Cons(value, Box::new(tail)))
+    }
+
+    pub fn iter(&self) -> ListIter<'_>
+    {
+        ListIter { next: self }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        *self == List::Nil
+    }
+
+    pub fn sum(&self) -> i32
+    {
+        self.iter().sum()
+    }
+}
+
+pub struct ListIter<'a>
+{
+    next: &'a List,
+}
+
+impl Iterator for ListIter<'_>
+{
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32>
+    {
+        match self.next
+        {
+            List::Cons(value, tail) => {
+                self.next = tail;
+                Some(*value)
+            }
+            List::Nil => None,
+        }
+    }
+}
+
 pub fn chapter_15_deref()
 {
     // Deref trait allows you to customize the behavior of the dereference operator *
@@ -7,6 +103,36 @@ pub fn chapter_15_deref()
     // By implementing Deref in such a way that a smart pointer can be treated like a regular reference, you can write code that operates on references and use that code with smart pointers too.
     // 
 
+    // following a reference, a Box and a MyBox, see deref_examples
+    let (y, z, my_box) = deref_examples();
+    println!("*y = {y}, *z = {z}, *my_box = {my_box}");
+
+    // #####################################################################
+    // ######## Implicit Deref Coercions with Functions and Methods ########
+    // #####################################################################
+    // 
+    // Deref coercion converts a reference to a type that implements the Deref trait into a reference to another type
+    // 
+    // For example, deref coercion can convert &String to &str because String implements the Deref trait such that it returns &str
+    // 
+    // It happens automatically, otherwise we woul need to add many explicit references and dereferences with & and *
+
+    // Exmpl with deref (hello is below this function):
+    let m = MyBox::new(String::from("some string"));
+    println!("{}", hello(&m)); // automatically &MyBox<String> converted to &str
+
+    // Without automatic deref coearcion we would need to do:
+    println!("{}", hello(&(*m)[..]));
+    // *m - we get the String
+    // &(*m) - we take ref of String (&String)
+    // &(*m)[..] - we take slice of whole string (&str)
+    
+
+}
+
+// What *y, *z and *my_box of chapter_15_deref give
+fn deref_examples() -> (i32, i32, i32)
+{
     // ########################################################################
     // ##################### HOW NORMAL REFERENCE WORK ########################
     // ########################################################################
@@ -30,31 +156,8 @@ pub fn chapter_15_deref()
     // ########################################################################
     // ##################### IMPLEMENTING SMART POINTER #######################
     // ########################################################################
-    // Box<T> type is defined as tuple struct with one elem
-
-    // Syntax of tuple struct: struct Name<T, U> (T, U, T, ....)
-    struct MyBox<T>(T);
-
-    impl<T> MyBox<T>
-    {
-        fn new(x: T) -> MyBox<T>
-        {
-            MyBox(x)
-        }
-    }
-
-    // Now we need to implement deref trait
-    impl<T> Deref for MyBox<T>
-    {
-        type Target = T; // associated type, what deref will return
-
-        fn deref(&self) -> &Self::Target 
-        {
-            // we return reference to the value stored in MyBox, so that we can
-            // use deref '*' operator
-            &self.0 
-        }
-    }
+    // Box<T> type is defined as tuple struct with one elem, MyBox at the
+    // top of this file is one too and implements Deref
 
     let my_box = MyBox::new(x);
 
@@ -64,34 +167,15 @@ pub fn chapter_15_deref()
     // when we did *my_box, in reality Rust did the following:
     // *(my_box.deref())
 
-    // #####################################################################
-    // ######## Implicit Deref Coercions with Functions and Methods ########
-    // #####################################################################
-    // 
-    // Deref coercion converts a reference to a type that implements the Deref trait into a reference to another type
-    // 
-    // For example, deref coercion can convert &String to &str because String implements the Deref trait such that it returns &str
-    // 
-    // It happens automatically, otherwise we woul need to add many explicit references and dereferences with & and *
-
-    fn hello(name: &str) {
-        println!("Hello, {name}!");
-    }
-
-    // Exmpl with deref:
-    let m = MyBox::new(String::from("some string"));
-    hello(&m); // automatically &MyBox<String> converted to &str
-
-    // Without automatic deref coearcion we would need to do:
-    hello(&(*m)[..]);
-    // *m - we get the String
-    // &(*m) - we take ref of String (&String)
-    // &(*m)[..] - we take slice of whole string (&str)
-    
+    (*y, *z, *my_box)
+}
 
+fn hello(name: &str) -> String
+{
+    format!("Hello, {name}!")
 }
 
-fn chapter_15_Box_pointer()
+pub fn chapter_15_Box_pointer()
 {
     // SMART POINTERS are data structures that act like a pointer, but also have additional metadata and capabilities
     // 
@@ -158,14 +242,48 @@ fn chapter_15_Box_pointer()
     // Pointer size doesn't change based on data it points
     // This means we can put a Box<T> inside the Cons variant instead of another List value directly. The Box<T> will point to the next List value that will be on the heap rather than inside the Cons variant
 
-    enum List {
-        Cons(i32, Box<List>), // tuple of int value and pointer to another List
-        Nil
-    }
+    // List at the top of this file is exactly that:
+    //
+    // enum List {
+    //     Cons(i32, Box<List>), // tuple of int value and pointer to another List
+    //     Nil
+    // }
     //
     // The Cons variant needs the size of an i32 plus the space to store the box’s pointer data
     // We now know that any List value will take up the size of an i32 plus the size of a box’s pointer data. By using a box, we’ve broken the infinite, recursive chain, so the compiler can figure out the size it needs to store a List value.
+    let lst = cons_list();
+    println!("cons list: {lst:?}");
+}
+
+// Exmpl: Cons(1, Cons(2, Cons(3, Nil))) with boxes
+fn cons_list() -> List
+{
     use List::{Cons, Nil};
 
-    let lst = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
-}
\ No newline at end of file
+    Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))))
+}
+
+// ############################## CHECKS ##################################
+
+pub fn chapter_15_box_checks() -> Vec<Check>
+{
+    let list = cons_list();
+
+    vec![
+        Check::new("cons list", List::from_slice(&[1, 2, 3]), list.clone()),
+        Check::new("cons list sum", 6, list.sum()),
+    ]
+}
+
+pub fn chapter_15_deref_checks() -> Vec<Check>
+{
+    let (y, z, my_box) = deref_examples();
+    let name = MyBox::new(String::from("Rust"));
+
+    vec![
+        Check::new("follow the reference", 5, y),
+        Check::new("Box implements Deref", 5, z),
+        Check::new("MyBox implements Deref", 5, my_box),
+        Check::new("deref coercion &MyBox<String> -> &str", "Hello, Rust!", hello(&name).as_str()),
+    ]
+}
@@ -1,3 +1,7 @@
+#![allow(unused, non_snake_case)]
+
+use crate::checks::Check;
+use crate::geometry;
 
 // To define a struct, we enter the keyword struct and name the entire struct.
 struct User {
@@ -7,18 +11,17 @@ struct User {
     sign_in_count: u64
 }
 
-pub fn chapter_6_3_if_let()
-{
-    // The if let syntax lets you combine if and let into a less verbose way to handle values that match one pattern while ignoring the rest
+// The coins of chapter 6, used by chapter_6_2_MATCH and chapter_6_3_if_let
+// and out here so the library can use them as well
 
-    #[derive(Debug)] 
-    enum UsState {
-        Alabama,
-        Alaska,
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsState {
+    Alabama,
+    Alaska,
+}
 
-    impl UsState {
-    fn existed_in(&self, year: u16) -> bool {
+impl UsState {
+    pub fn existed_in(&self, year: u16) -> bool {
         match self {
             UsState::Alabama => year >= 1819,
             UsState::Alaska => year >= 1959,
@@ -26,13 +29,35 @@ pub fn chapter_6_3_if_let()
     }
 }
 
-    enum Coin {
-        Penny,
-        Nickel,
-        Dime,
-        Quarter(UsState), 
-        // enums can have values and in match expr we can get them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+    // enums can have values and in match expr we can get them
+}
+
+impl Coin {
+    pub fn value_in_cents(&self) -> u8 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Nickel => 5,
+            Coin::Dime => 10,
+            // here we could get the inner state of Quarter, but every
+            // state's quarter is worth the same
+            Coin::Quarter(_) => 25,
+        }
     }
+}
+
+// starts with the match that if let replaces
+#[allow(clippy::single_match)]
+pub fn chapter_6_3_if_let()
+{
+    // The if let syntax lets you combine if and let into a less verbose way to handle values that match one pattern while ignoring the rest
+
+    // Coin and UsState are at the top of this file
 
     // so instead of writing below:
     let config_max = Some(8);
@@ -84,23 +109,22 @@ pub fn chapter_6_3_if_let()
 
 }
 
+// the book's match example, Option::map would do the same
+#[allow(clippy::manual_map)]
+fn plus_one(x: Option<i32>) -> Option<i32> {
+    // !!! the arms’ patterns must cover all possibilities
+    match x {
+        None => None,
+        Some(i) => Some(i + 1),
+    }
+}
+
 pub fn chapter_6_2_MATCH()
 {
     // Values go through each pattern in a match, and at the first pattern the value “fits,” the value falls into the associated code block to be used during execution
 
-    #[derive(Debug)] 
-    enum UsState {
-        Alabama,
-        Alaska,
-    }
-
-    enum Coin {
-        Penny,
-        Nickel,
-        Dime,
-        Quarter(UsState), 
-        // enums can have values and in match expr we can get them
-    }
+    // Coin and UsState are at the top of this file, with Coin::value_in_cents
+    // matching on the coin like this function does
 
     fn value_in_cents(coin: Coin) -> u8 {
         match coin {
@@ -121,14 +145,8 @@ pub fn chapter_6_2_MATCH()
     let one_quarter = Coin::Quarter(UsState::Alabama);
     value_in_cents(one_quarter);
 
-    // we do the same with Option
-    fn plus_one(x: Option<i32>) -> Option<i32> {
-        // !!! the arms’ patterns must cover all possibilities
-        match x {
-            None => None,
-            Some(i) => Some(i + 1),
-        }
-    }
+    // we do the same with Option, see plus_one below this function
+    println!("plus_one(Some(5)) = {:?}, plus_one(None) = {:?}", plus_one(Some(5)), plus_one(None));
 
     let five = Some(5);
     let six = plus_one(five);
@@ -266,15 +284,66 @@ pub fn chapter_6_1_enums()
     }
 }
 
-pub fn chapter_5_3_struct_methods()
+// The Rectangle of chapter_5_3_struct_methods, with its impl blocks, out
+// here so the checks can call its methods too. The library's Rectangle,
+// with a position and can_hold, is geometry::Rectangle.
+#[derive(Debug, PartialEq)]
+struct Rectangle {
+    width: u32, // these are currently public
+    height: u32,
+}
+
+// To implement methods for given struct we use 'impl' word
+// Everything within this 'impl' block will be associated with the Rectangle type
+impl Rectangle 
 {
+    // The &self is actually short for self: &Self. Within an impl block, 
+    // the type Self is an alias for the type that the impl block is for. 
+    // --> Methods MUST HAVE A PARAMETER NAMED SELF of type Self FOR THEIR FIRST PARAMETER
+    fn area(&self) -> u32 
+    {
+        // We chose &self here for the same reason we used &Rectangle in the function version: we don’t want to take ownership, and we just want to read the data in the struct, not write to it
+        self.width * self.height
+    }
+
+    // Having a method that TAKES OWNERSHIP BY USING JUST SELF is rare; this technique is usually used when the method transforms self into something else and you want to prevent the caller from using the original instance after the transformation.
 
-    #[derive(Debug)]
-    struct Rectangle {
-        width: u32, // these are currently public
-        height: u32,
+    // We can define getters; Rust does not implement them automatically for struct fields as some other languages do
+    fn width(&self) -> u32 
+    {
+        self.width
     }
 
+}
+
+// exmpl for the -> operator part of the lesson:
+impl Rectangle 
+{
+    // u32 can't go below 0, plain '-' panics in debug builds (and
+    // wraps in release) when rect is the bigger one, checked_sub
+    // gives None instead. The areas are checked too, they don't fit
+    // in u32 for big rectangles (geometry::Rectangle works in u64).
+    fn diff(&self, rect: &Rectangle) -> Option<u32>
+    {
+        let area = self.width.checked_mul(self.height)?;
+        area.checked_sub(rect.width.checked_mul(rect.height)?)
+    }
+}
+
+// Associated functions
+impl Rectangle
+{
+    // The Self keywords in the return type and in the body of the function are aliases for the type that appears after the impl keyword, which in this case is Rectangle
+    fn square(x: u32) -> Self
+    {
+        Self { width: x, height: x }
+    }
+}
+
+// borrows rect1 by hand to show what method calls do automatically
+#[allow(clippy::needless_borrow)]
+pub fn chapter_5_3_struct_methods()
+{
     // Struct Simple methods
     {
         println!("################ STRUCT SIMPLE METHODS ################");
@@ -283,28 +352,8 @@ pub fn chapter_5_3_struct_methods()
         // their first parameter is always SELF, which represents the instance of the struct the method is being called on (like in python).
 
 
-        // To implement methods for given struct we use 'impl' word
-        // Everything within this 'impl' block will be associated with the Rectangle type
-        impl Rectangle 
-        {
-            // The &self is actually short for self: &Self. Within an impl block, 
-            // the type Self is an alias for the type that the impl block is for. 
-            // --> Methods MUST HAVE A PARAMETER NAMED SELF of type Self FOR THEIR FIRST PARAMETER
-            fn area(&self) -> u32 
-            {
-                // We chose &self here for the same reason we used &Rectangle in the function version: we don’t want to take ownership, and we just want to read the data in the struct, not write to it
-                self.width * self.height
-            }
-
-            // Having a method that TAKES OWNERSHIP BY USING JUST SELF is rare; this technique is usually used when the method transforms self into something else and you want to prevent the caller from using the original instance after the transformation.
-
-            // We can define getters; Rust does not implement them automatically for struct fields as some other languages do
-            fn width(&self) -> u32 
-            {
-                self.width
-            }
-
-        }
+        // To implement methods for given struct we use 'impl' word, see the
+        // impl blocks above this function
 
         let rect1 = Rectangle {
             width: 30,
@@ -333,16 +382,7 @@ pub fn chapter_5_3_struct_methods()
         // This automatic referencing behavior works because METHODS HAVE A CLEAR RECEIVER—THE TYPE OF SELF
         // Rust can figure out definitively whether the method is reading      (&self), mutating (&mut self), or consuming (self)
 
-        // exmpl:
-        impl Rectangle 
-        {
-            fn diff(&self, rect: &Rectangle) -> u32
-            {
-                self.width * self.height - rect.width * rect.height
-            }
-        }
-
-
+        // exmpl, with diff from the impl blocks above this function:
         let rect1 = Rectangle {
             width: 30,
             height: 50,
@@ -353,7 +393,7 @@ pub fn chapter_5_3_struct_methods()
             height: 20,
         };
 
-        println!("diff of surfaces: {}", (&rect1).diff(&rect2)); 
+        println!("diff of surfaces: {:?}", (&rect1).diff(&rect2)); 
         // the same as rect1.diff(&rect2)
         // we wrote: (&rect1).diff(&rect2), since in diff signature we 
         // have &self, so our &rect1 is this &self
@@ -363,16 +403,7 @@ pub fn chapter_5_3_struct_methods()
     {
         // All functions defined within an impl block are called associated functions
 
-        // We can DEFINE ASSOCIATED FUNCTIONS THAT DON’T HAVE SELF as their first parameter (and thus are not methods) because they don’t need an instance of the type to work with
-
-        impl Rectangle
-        {
-            // The Self keywords in the return type and in the body of the function are aliases for the type that appears after the impl keyword, which in this case is Rectangle
-            fn square(x: u32) -> Self
-            {
-                Self { width: x, height: x }
-            }
-        }
+        // We can DEFINE ASSOCIATED FUNCTIONS THAT DON’T HAVE SELF as their first parameter (and thus are not methods) because they don’t need an instance of the type to work with, like square in the impl blocks above this function
 
         let sq1 = Rectangle::square(69);
 
@@ -520,6 +551,7 @@ pub fn chapter_5_1_structs()
         println!("User2 mail: {}", user2_mut.email);
 
         // email and username will have their ownership transferred to the User
+        #[allow(clippy::redundant_field_names)]
         fn bad_build_user(email: String, username: String) -> User 
         {
             User {
@@ -626,4 +658,38 @@ pub fn chapter_5_1_structs()
         // later we’ll implement behavior for this type such that every instance of AlwaysEqual is always equal to every instance of any other type
     }
 
-}
\ No newline at end of file
+}
+
+// ############################## CHECKS ##################################
+
+pub fn chapter_5_3_struct_methods_checks() -> Vec<Check>
+{
+    let rect1 = Rectangle { width: 30, height: 50 };
+    let rect2 = Rectangle { width: 10, height: 40 };
+    let huge = Rectangle { width: u32::MAX, height: 2 };
+
+    // can_hold is the library's, the lesson doesn't write it
+    let big = geometry::Rectangle::new(30, 50);
+
+    vec![
+        Check::new("area method", 1500, rect1.area()),
+        Check::new("width getter", 30, rect1.width()),
+        Check::new("diff of surfaces", Some(1100), rect1.diff(&rect2)),
+        Check::new("diff doesn't underflow", None, rect2.diff(&rect1)),
+        Check::new("diff doesn't overflow", None, huge.diff(&rect1)),
+        Check::new("associated function square", Rectangle { width: 3, height: 3 }, Rectangle::square(3)),
+        Check::new("rect1 can hold rect2", true, big.can_hold(&geometry::Rectangle::new(10, 40))),
+        Check::new("rect1 can't hold rect3", false, big.can_hold(&geometry::Rectangle::new(60, 45))),
+    ]
+}
+
+pub fn chapter_6_2_match_checks() -> Vec<Check>
+{
+    vec![
+        Check::new("penny", 1, Coin::Penny.value_in_cents()),
+        Check::new("dime", 10, Coin::Dime.value_in_cents()),
+        Check::new("quarter from any state", 25, Coin::Quarter(UsState::Alaska).value_in_cents()),
+        Check::new("plus_one of Some", Some(6), plus_one(Some(5))),
+        Check::new("plus_one of None", None, plus_one(None)),
+    ]
+}
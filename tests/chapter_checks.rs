@@ -0,0 +1,32 @@
+// Every check a chapter has must pass, the same as `cargo run -- check`
+
+use std::collections::HashSet;
+
+use rust_progr_lang_course::chapters::CHAPTERS;
+
+#[test]
+fn every_chapter_check_passes()
+{
+    let mut failures = Vec::new();
+
+    for chapter in CHAPTERS
+    {
+        for c in (chapter.checks)().iter().filter(|c| !c.passed)
+        {
+            failures.push(format!("{} / {}: expected {}, got {}", chapter.name, c.name, c.expected, c.actual));
+        }
+    }
+
+    assert!(failures.is_empty(), "failing checks:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn check_names_are_unique_within_a_chapter()
+{
+    for chapter in CHAPTERS
+    {
+        let checks = (chapter.checks)();
+        let names: HashSet<&str> = checks.iter().map(|c| c.name).collect();
+        assert_eq!(names.len(), checks.len(), "duplicate check names in {}", chapter.name);
+    }
+}
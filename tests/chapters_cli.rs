@@ -0,0 +1,25 @@
+// The chapters binary's command line
+
+use std::process::Command;
+
+const BIN: &str = env!("CARGO_BIN_EXE_chapters");
+
+#[test]
+fn run_all_takes_no_chapter_names()
+{
+    for args in [["run", "3", "--all"], ["run", "--all", "8"]]
+    {
+        let output = Command::new(BIN).args(args).output().unwrap();
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("can't be combined"));
+    }
+}
+
+#[test]
+fn unknown_chapter_is_an_error()
+{
+    let output = Command::new(BIN).args(["run", "no-such-chapter"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown chapter 'no-such-chapter'"));
+}
@@ -0,0 +1,205 @@
+// k-means, DBSCAN and loading points from CSV
+
+use std::collections::HashMap;
+use std::{env, fs, process};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{dbscan, kmeans, load_points_csv, parse_points_csv, DbscanConfig, KMeansConfig, Point};
+
+// `count` points in a square of `spread` around each center
+fn blobs(rng: &mut StdRng, centers: &[(f64, f64)], count: usize, spread: f64) -> Vec<Point<f64>>
+{
+    let mut points = Vec::new();
+    for &(x, y) in centers
+    {
+        for _ in 0..count
+        {
+            points.push(Point::new(x + rng.gen_range(-spread..spread), y + rng.gen_range(-spread..spread)));
+        }
+    }
+    points
+}
+
+#[test]
+fn kmeans_finds_separate_blobs()
+{
+    let mut rng = StdRng::seed_from_u64(49);
+    let centers = [(0.0, 0.0), (100.0, 0.0), (0.0, 100.0), (100.0, 100.0)];
+    let points = blobs(&mut rng, &centers, 50, 5.0);
+
+    let config = KMeansConfig { seed: Some(7), ..KMeansConfig::new(4) };
+    let clustering = kmeans(&points, &config);
+    assert_eq!(clustering.cluster_count(), 4);
+
+    // every blob is one cluster, and no two blobs share one
+    let mut clusters = vec![0; centers.len()];
+    for (blob, chunk) in clustering.assignments.chunks(50).enumerate()
+    {
+        assert!(chunk.iter().all(|a| *a == chunk[0]), "blob {blob} is split");
+        clusters[blob] = chunk[0].unwrap();
+    }
+    clusters.sort();
+    clusters.dedup();
+    assert_eq!(clusters.len(), 4);
+
+    for (cluster, centroid) in clustering.centroids.iter().enumerate()
+    {
+        let members: Vec<usize> = clustering.members(cluster).collect();
+        let mean_x = members.iter().map(|&i| points[i].x).sum::<f64>() / members.len() as f64;
+        let mean_y = members.iter().map(|&i| points[i].y).sum::<f64>() / members.len() as f64;
+        assert!((centroid.x - mean_x).abs() < 1e-9 && (centroid.y - mean_y).abs() < 1e-9);
+    }
+
+    let inertia: f64 = points.iter().zip(&clustering.assignments).map(|(p, a)| p.distance_squared(clustering.centroids[a.unwrap()])).sum();
+    assert!((clustering.inertia - inertia).abs() < 1e-6);
+
+    // the same seed, the same clusters
+    assert_eq!(kmeans(&points, &config), clustering);
+}
+
+#[test]
+fn kmeans_settles_on_nearest_centroids()
+{
+    let mut rng = StdRng::seed_from_u64(5);
+
+    for round in 0..20
+    {
+        let count = rng.gen_range(1..300);
+        let points: Vec<Point<f64>> = (0..count).map(|_| Point::new(rng.gen_range(-50.0..50.0), rng.gen_range(-50.0..50.0))).collect();
+        let clustering = kmeans(&points, &KMeansConfig { seed: Some(round), ..KMeansConfig::new(1 + round as usize % 7) });
+
+        // once settled every point is with its nearest centroid
+        for (p, a) in points.iter().zip(&clustering.assignments)
+        {
+            let own = p.distance_squared(clustering.centroids[a.unwrap()]);
+            assert!(clustering.centroids.iter().all(|c| own <= p.distance_squared(*c)));
+        }
+    }
+}
+
+#[test]
+fn kmeans_edge_cases()
+{
+    let same = vec![Point::new(1.0, 1.0); 5];
+    let clustering = kmeans(&same, &KMeansConfig { seed: Some(1), ..KMeansConfig::new(3) });
+    assert_eq!(clustering.centroids, vec![Point::new(1.0, 1.0)]);
+    assert_eq!(clustering.inertia, 0.0);
+
+    let with_nan = [Point::new(0.0, 0.0), Point::new(f64::NAN, 1.0), Point::new(2.0, 0.0)];
+    let clustering = kmeans(&with_nan, &KMeansConfig { seed: Some(1), ..KMeansConfig::new(1) });
+    assert_eq!(clustering.assignments, vec![Some(0), None, Some(0)]);
+    assert_eq!(clustering.centroids, vec![Point::new(1.0, 0.0)]);
+
+    assert_eq!(kmeans(&[], &KMeansConfig::new(3)).cluster_count(), 0);
+    assert_eq!(kmeans(&same, &KMeansConfig::new(0)).assignments, vec![None; 5]);
+}
+
+// Looks at every pair of points
+fn core_points(points: &[Point<f64>], config: &DbscanConfig) -> Vec<bool>
+{
+    points
+        .iter()
+        .map(|p| points.iter().filter(|q| p.distance(**q) <= config.epsilon).count() >= config.min_points)
+        .collect()
+}
+
+#[test]
+fn dbscan_matches_brute_force()
+{
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..20
+    {
+        let count = rng.gen_range(0..250);
+        let points: Vec<Point<f64>> = (0..count).map(|_| Point::new(rng.gen_range(0.0..100.0), rng.gen_range(0.0..100.0))).collect();
+        let config = DbscanConfig { epsilon: rng.gen_range(2.0..10.0), min_points: rng.gen_range(1..6) };
+        let clustering = dbscan(&points, &config);
+        let core = core_points(&points, &config);
+        let near = |i: usize, j: usize| points[i].distance(points[j]) <= config.epsilon;
+
+        for i in 0..points.len()
+        {
+            for j in 0..points.len()
+            {
+                // neighbouring core points are in the same cluster
+                if core[i] && core[j] && near(i, j)
+                {
+                    assert_eq!(clustering.assignments[i], clustering.assignments[j]);
+                }
+            }
+
+            match clustering.assignments[i]
+            {
+                // noise is far from every core point
+                None => assert!((0..points.len()).all(|j| !(core[j] && near(i, j)))),
+                // the rest is a core point or next to one of its cluster
+                Some(cluster) => assert!(
+                    core[i] || (0..points.len()).any(|j| core[j] && near(i, j) && clustering.assignments[j] == Some(cluster))
+                ),
+            }
+        }
+
+        // no cluster is left without points
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        for cluster in clustering.assignments.iter().flatten()
+        {
+            *sizes.entry(*cluster).or_default() += 1;
+        }
+        assert_eq!(sizes.len(), clustering.cluster_count());
+    }
+}
+
+#[test]
+fn dbscan_leaves_noise_out()
+{
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut points = blobs(&mut rng, &[(0.0, 0.0), (50.0, 50.0)], 30, 2.0);
+    points.push(Point::new(25.0, 25.0));
+    points.push(Point::new(-40.0, 60.0));
+
+    let clustering = dbscan(&points, &DbscanConfig { epsilon: 3.0, min_points: 4 });
+    assert_eq!(clustering.cluster_count(), 2);
+    assert_eq!(clustering.noise().collect::<Vec<_>>(), vec![60, 61]);
+    assert!(clustering.assignments[..30].iter().all(|a| *a == Some(0)));
+    assert!(clustering.assignments[30..60].iter().all(|a| *a == Some(1)));
+    assert!(clustering.centroids[1].distance(Point::new(50.0, 50.0)) < 1.0);
+}
+
+#[test]
+fn points_from_csv()
+{
+    let points = parse_points_csv("1,2\n\n3.5, -4\n\"5\",6,extra\n").unwrap();
+    assert_eq!(points, vec![Point::new(1.0, 2.0), Point::new(3.5, -4.0), Point::new(5.0, 6.0)]);
+
+    // named columns, in any order
+    let points = parse_points_csv("name,Lat,Lon\nhome,52.25,21.0\nwork,52.5,20.75\n").unwrap();
+    assert_eq!(points, vec![Point::new(21.0, 52.25), Point::new(20.75, 52.5)]);
+    let points = parse_points_csv("a,b\n1,2\n").unwrap();
+    assert_eq!(points, vec![Point::new(1.0, 2.0)]);
+    // no header, the numbers after a name column are lon then lat and the
+    // first row is kept
+    let headerless = parse_points_csv("home,21.0,52.25\nwork,20.75,52.5\n").unwrap();
+    assert_eq!(headerless, vec![Point::new(21.0, 52.25), Point::new(20.75, 52.5)]);
+    // the same points with a header, in either column order
+    assert_eq!(parse_points_csv("name,lon,lat\nhome,21.0,52.25\nwork,20.75,52.5\n").unwrap(), headerless);
+    assert_eq!(parse_points_csv("name,lat,lon\nhome,52.25,21.0\nwork,52.5,20.75\n").unwrap(), headerless);
+
+    let error = |text: &str| parse_points_csv(text).unwrap_err().to_string();
+    assert_eq!(error("x,y\n1,2\n3\n"), "<input>:3: expected at least 2 columns, found 1");
+    assert_eq!(error("1,2\n\n1,two\n"), "<input>:3: 'two' in column 2 is not a number");
+    assert_eq!(error("1,NaN\n"), "<input>:1: 'NaN' in column 2 is not a number");
+    assert_eq!(error("home,52.23\n"), "<input>:1: 'home' in column 1 is not a number");
+
+    let dir = env::temp_dir().join(format!("points-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("events.csv");
+    fs::write(&file, "x,y\n0,0\n1,1\n").unwrap();
+    assert_eq!(load_points_csv(&file).unwrap().len(), 2);
+    fs::write(&file, "x,y\n0,0\n1,\n").unwrap();
+    assert_eq!(load_points_csv(&file).unwrap_err().to_string(), format!("{}:3: '' in column 2 is not a number", file.display()));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let missing = load_points_csv(&dir.join("missing.csv")).unwrap_err();
+    assert_eq!(missing.line, 0);
+}
@@ -0,0 +1,195 @@
+// Hulls, closest pairs, polygons and segments, checked against brute force
+// and against exact integer arithmetic
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{
+    closest_pair,
+    convex_hull,
+    orientation,
+    Containment,
+    Orientation,
+    Point,
+    Polygon,
+    Segment,
+    SegmentIntersection,
+};
+
+fn random_points(rng: &mut StdRng, count: usize, spread: i64) -> Vec<Point<i64>>
+{
+    (0..count).map(|_| Point::new(rng.gen_range(-spread..spread), rng.gen_range(-spread..spread))).collect()
+}
+
+#[test]
+fn float_orientation_is_exact()
+{
+    // Points a hair away from the line through b and c, 2^-53 apart. Scaled
+    // by 2^53 they're integers, where the answer is exact.
+    let scale = (1u64 << 53) as f64;
+    let (b, c) = (Point::new(12.0, 12.0), Point::new(24.0, 24.0));
+    let (wide_b, wide_c) = (Point::new(12i64 << 53, 12i64 << 53), Point::new(24i64 << 53, 24i64 << 53));
+    let mut naive_wrong = 0;
+
+    for i in 0..64
+    {
+        for j in 0..64
+        {
+            let a = Point::new(0.5 + f64::from(i) / scale, 0.5 + f64::from(j) / scale);
+            let wide_a = Point::new((1i64 << 52) + i64::from(i), (1i64 << 52) + i64::from(j));
+
+            let exact = orientation(wide_a, wide_b, wide_c);
+            assert_eq!(orientation(a, b, c), exact, "{a:?}");
+
+            let naive = ((b - a).cross(c - a)).partial_cmp(&0.0).unwrap();
+            let naive_matches = match exact
+            {
+                Orientation::CounterClockwise => naive.is_gt(),
+                Orientation::Clockwise => naive.is_lt(),
+                Orientation::Collinear => naive.is_eq(),
+            };
+            naive_wrong += usize::from(!naive_matches);
+        }
+    }
+
+    // otherwise this test wouldn't be testing anything
+    assert!(naive_wrong > 0);
+    assert_eq!(orientation(Point::new(0u32, 0), Point::new(u32::MAX, 0), Point::new(0, u32::MAX)), Orientation::CounterClockwise);
+    assert_eq!(orientation(Point::new(i64::MIN, i64::MIN), Point::new(i64::MAX, i64::MAX), Point::new(0, 0)), Orientation::Collinear);
+    assert_eq!(orientation(Point::new(i64::MIN, i64::MIN), Point::new(i64::MAX, i64::MAX), Point::new(-1, 0)), Orientation::CounterClockwise);
+}
+
+#[test]
+fn hull_holds_every_point_and_is_convex()
+{
+    let mut rng = StdRng::seed_from_u64(3);
+
+    for round in 0..200
+    {
+        let count = rng.gen_range(0..60);
+        let points = random_points(&mut rng, count, if round % 2 == 0 { 10 } else { 1000 });
+        let hull = convex_hull(&points);
+        let n = hull.vertices.len();
+
+        for v in &hull.vertices
+        {
+            assert!(points.contains(v));
+        }
+        if n < 3
+        {
+            continue;
+        }
+
+        for i in 0..n
+        {
+            let turn = orientation(hull.vertices[i], hull.vertices[(i + 1) % n], hull.vertices[(i + 2) % n]);
+            assert_eq!(turn, Orientation::CounterClockwise, "{hull:?}");
+        }
+        for &p in &points
+        {
+            assert_ne!(hull.contains(p), Containment::Outside, "{p:?} {hull:?}");
+        }
+    }
+}
+
+#[test]
+fn hull_of_special_cases()
+{
+    let square = [(0, 0), (2, 0), (2, 2), (0, 2), (1, 1), (1, 0), (0, 0)].map(Point::from);
+    assert_eq!(convex_hull(&square).vertices, [(0, 0), (2, 0), (2, 2), (0, 2)].map(Point::from));
+
+    let line = [(3, 3), (1, 1), (2, 2)].map(Point::from);
+    assert_eq!(convex_hull(&line).vertices, [(1, 1), (3, 3)].map(Point::from));
+
+    let with_nan = [(0.0, 0.0), (f64::NAN, 5.0), (1.0, 0.0), (0.0, 1.0)].map(Point::from);
+    assert_eq!(convex_hull(&with_nan).vertices.len(), 3);
+    assert!(convex_hull::<f64>(&[]).vertices.is_empty());
+}
+
+#[test]
+fn closest_pair_matches_brute_force()
+{
+    let mut rng = StdRng::seed_from_u64(4);
+
+    for round in 0..200
+    {
+        let count = rng.gen_range(0..200);
+        let points = random_points(&mut rng, count, if round % 2 == 0 { 20 } else { 100_000 });
+        let brute = (0..points.len())
+            .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
+            .map(|(i, j)| points[i].distance_squared(points[j]))
+            .min_by(f64::total_cmp);
+
+        let found = closest_pair(&points).map(|(a, b)| a.distance_squared(b));
+        assert_eq!(found, brute, "{points:?}");
+
+        let floats: Vec<Point<f64>> = points.iter().map(|p| p.cast().unwrap()).collect();
+        assert_eq!(closest_pair(&floats).map(|(a, b)| a.distance_squared(b)), brute);
+    }
+}
+
+#[test]
+fn polygon_area_centroid_and_containment()
+{
+    // an L: a 2x2 square with a 1x1 square cut out of its top right
+    let l = Polygon::new([(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)].map(Point::from).to_vec());
+    let clockwise = Polygon::new(l.vertices.iter().rev().copied().collect());
+
+    assert_eq!(l.signed_area(), 3.0);
+    assert_eq!(clockwise.signed_area(), -3.0);
+    assert_eq!(l.perimeter(), 8.0);
+    let centroid = l.centroid().unwrap();
+    assert!((centroid.x - 5.0 / 6.0).abs() < 1e-12 && (centroid.y - 5.0 / 6.0).abs() < 1e-12);
+    assert_eq!(Polygon::new([(0, 0), (1, 1), (2, 2)].map(Point::from).to_vec()).centroid(), None);
+
+    for polygon in [&l, &clockwise]
+    {
+        assert_eq!(polygon.contains(Point::new(0, 0)), Containment::OnBoundary);
+        assert_eq!(polygon.contains(Point::new(1, 2)), Containment::OnBoundary);
+        assert_eq!(polygon.contains(Point::new(2, 2)), Containment::Outside);
+        assert_eq!(polygon.contains(Point::new(3, 1)), Containment::Outside);
+    }
+
+    let scaled = Polygon::new(l.vertices.iter().map(|&p| p * 2).collect());
+    assert_eq!(scaled.contains(Point::new(1, 1)), Containment::Inside);
+    assert_eq!(scaled.contains(Point::new(3, 3)), Containment::Outside);
+    assert_eq!(scaled.contains(Point::new(3, 1)), Containment::Inside);
+}
+
+#[test]
+fn segment_intersections()
+{
+    let s = |a: (i32, i32), b: (i32, i32)| Segment::new(Point::from(a), Point::from(b));
+
+    assert_eq!(s((0, 0), (2, 2)).intersection(&s((0, 2), (2, 0))), Some(SegmentIntersection::Point(Point::new(1.0, 1.0))));
+    assert_eq!(s((0, 0), (2, 0)).intersection(&s((2, 0), (3, 5))), Some(SegmentIntersection::Point(Point::new(2.0, 0.0))));
+    assert_eq!(s((0, 0), (2, 0)).intersection(&s((0, 1), (2, 1))), None);
+    assert_eq!(s((0, 0), (4, 0)).intersection(&s((6, 0), (2, 0))), Some(SegmentIntersection::Overlap(s((2, 0), (4, 0)))));
+    assert_eq!(s((0, 0), (1, 1)).intersection(&s((2, 2), (3, 3))), None);
+    assert_eq!(s((0, 0), (1, 1)).intersection(&s((1, 1), (3, 3))), Some(SegmentIntersection::Point(Point::new(1.0, 1.0))));
+    assert_eq!(s((1, 1), (1, 1)).intersection(&s((0, 0), (2, 2))), Some(SegmentIntersection::Point(Point::new(1.0, 1.0))));
+    assert!(!s((0, 0), (1, 0)).intersects(&s((2, -1), (2, 1))));
+}
+
+#[test]
+fn segments_agree_with_brute_force()
+{
+    let mut rng = StdRng::seed_from_u64(5);
+
+    for _ in 0..2000
+    {
+        let p = random_points(&mut rng, 4, 4);
+        let (a, b) = (Segment::new(p[0], p[1]), Segment::new(p[2], p[3]));
+
+        // walk both segments in tiny steps, scaled up so every step is whole
+        let steps = 24;
+        let on = |seg: &Segment<i64>, t: i64| seg.start * steps + (seg.end - seg.start) * t;
+        let touching = (0..=steps).any(|i| (0..=steps).any(|j| on(&a, i) == on(&b, j)));
+
+        if touching
+        {
+            assert!(a.intersects(&b), "{a:?} {b:?}");
+        }
+        assert_eq!(a.intersects(&b), b.intersects(&a));
+        assert_eq!(a.intersects(&b), a.intersection(&b).is_some());
+    }
+}
@@ -0,0 +1,168 @@
+// Properties of positioned rectangles, checked on many seeded random cases
+// so a failure can be reproduced
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::Rectangle;
+
+const CASES: usize = 2000;
+
+// Mostly small rectangles close to each other, so they overlap often, and
+// now and then one near the ends of i32 to catch overflows
+fn rectangle(rng: &mut StdRng) -> Rectangle
+{
+    if rng.gen_ratio(1, 10)
+    {
+        let x = if rng.r#gen() { i32::MAX - rng.gen_range(0..100) } else { i32::MIN + rng.gen_range(0..100) };
+        let y = if rng.r#gen() { i32::MAX - rng.gen_range(0..100) } else { i32::MIN + rng.gen_range(0..100) };
+        Rectangle::at(x, y, rng.r#gen(), rng.r#gen())
+    }
+    else
+    {
+        Rectangle::at(rng.gen_range(-20..20), rng.gen_range(-20..20), rng.gen_range(0..30), rng.gen_range(0..30))
+    }
+}
+
+fn pairs(seed: u64) -> impl Iterator<Item = (Rectangle, Rectangle, StdRng)>
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..CASES).map(move |_| {
+        let a = rectangle(&mut rng);
+        let b = rectangle(&mut rng);
+        (a, b, StdRng::seed_from_u64(rng.r#gen()))
+    })
+}
+
+#[test]
+fn intersection_is_symmetric_and_inside_both()
+{
+    for (a, b, _) in pairs(1)
+    {
+        assert_eq!(a.intersection(&b), b.intersection(&a), "{a:?} {b:?}");
+        assert_eq!(a.intersects(&b), a.intersection(&b).is_some(), "{a:?} {b:?}");
+
+        if let Some(both) = a.intersection(&b)
+        {
+            assert!(!both.is_empty(), "{a:?} {b:?} -> {both:?}");
+            assert!(a.contains_rect(&both) && b.contains_rect(&both), "{a:?} {b:?} -> {both:?}");
+            assert!(both.area() <= a.area().min(b.area()));
+        }
+    }
+}
+
+#[test]
+fn points_in_the_intersection_are_in_both()
+{
+    for (a, b, mut rng) in pairs(2)
+    {
+        for _ in 0..20
+        {
+            let (x, y) = (rng.gen_range(-25..55), rng.gen_range(-25..55));
+            let in_both = a.contains_point(x, y) && b.contains_point(x, y);
+            let in_intersection = a.intersection(&b).is_some_and(|r| r.contains_point(x, y));
+
+            assert_eq!(in_both, in_intersection, "{a:?} {b:?} ({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn union_bounds_hold_both()
+{
+    for (a, b, mut rng) in pairs(3)
+    {
+        let Some(union) = a.union_bounds(&b) else {
+            // only possible when they are further apart than u32::MAX
+            assert!(a.right().max(b.right()) - a.left().min(b.left()) > i64::from(u32::MAX)
+                || a.bottom().max(b.bottom()) - a.top().min(b.top()) > i64::from(u32::MAX));
+            continue;
+        };
+
+        assert_eq!(Some(union), b.union_bounds(&a));
+        assert!(union.contains_rect(&a) && union.contains_rect(&b), "{a:?} {b:?} -> {union:?}");
+        assert!(union.area() >= a.area().max(b.area()));
+
+        let (x, y) = (rng.gen_range(-25..55), rng.gen_range(-25..55));
+        if a.contains_point(x, y) || b.contains_point(x, y)
+        {
+            assert!(union.contains_point(x, y), "{union:?} ({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn contains_rect_agrees_with_intersection()
+{
+    for (a, b, _) in pairs(4)
+    {
+        if a.contains_rect(&b) && !b.is_empty()
+        {
+            assert_eq!(a.intersection(&b), Some(b), "{a:?} {b:?}");
+        }
+        if a.contains_rect(&b) && b.contains_rect(&a)
+        {
+            assert_eq!(a, b);
+        }
+        assert!(a.contains_rect(&a));
+    }
+}
+
+#[test]
+fn checked_area_diff_never_underflows()
+{
+    for (a, b, _) in pairs(5)
+    {
+        match a.checked_area_diff(&b)
+        {
+            Some(diff) => assert_eq!(diff + b.area(), a.area()),
+            None => assert!(a.area() < b.area()),
+        }
+    }
+}
+
+#[test]
+fn translate_is_checked()
+{
+    for (a, _, mut rng) in pairs(6)
+    {
+        // -i32::MIN doesn't exist, so the way back is kept in range
+        let (dx, dy) = (rng.gen_range(-i32::MAX..=i32::MAX), rng.gen_range(-i32::MAX..=i32::MAX));
+        match a.checked_translate(dx, dy)
+        {
+            Some(moved) => {
+                assert_eq!((moved.width, moved.height), (a.width, a.height));
+                assert_eq!(moved.checked_translate(-dx, -dy), Some(a));
+            }
+            None => assert!(a.x.checked_add(dx).is_none() || a.y.checked_add(dy).is_none()),
+        }
+    }
+}
+
+#[test]
+fn shared_edges_and_corners()
+{
+    let a = Rectangle::at(0, 0, 10, 10);
+
+    assert!(!a.intersects(&Rectangle::at(10, 0, 5, 5)));
+    assert!(!a.intersects(&Rectangle::at(10, 10, 5, 5)));
+    assert!(a.intersects(&Rectangle::at(9, 9, 5, 5)));
+    assert!(a.contains_point(0, 0) && !a.contains_point(10, 5));
+    assert!(a.contains_rect(&Rectangle::at(5, 5, 5, 5)));
+    // can_hold only compares the sides, and they must be strictly bigger
+    assert!(!a.can_hold(&Rectangle::at(0, 0, 10, 5)) && a.can_hold(&Rectangle::at(50, 50, 5, 5)));
+    assert_eq!(a.union_bounds(&Rectangle::at(20, -5, 1, 1)), Some(Rectangle::at(0, -5, 21, 15)));
+}
+
+#[test]
+fn extremes_do_not_overflow()
+{
+    let max = Rectangle::at(i32::MAX, i32::MAX, u32::MAX, u32::MAX);
+    let min = Rectangle::at(i32::MIN, i32::MIN, u32::MAX, u32::MAX);
+
+    assert_eq!(max.area(), u64::from(u32::MAX) * u64::from(u32::MAX));
+    assert!(max.contains_point(i32::MAX, i32::MAX));
+    assert!(min.contains_point(i32::MAX - 1, i32::MIN));
+    assert_eq!(min.intersection(&max), None);
+    assert_eq!(min.union_bounds(&max), None);
+    assert_eq!(max.checked_translate(1, 0), None);
+}
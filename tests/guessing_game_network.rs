@@ -0,0 +1,251 @@
+// Drives `guessing_game serve` with stand-in clients speaking the line protocol
+// directly over TCP, so every message the server sends is checked.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use rust_progr_lang_course::guessing_game::{serve, GameConfig};
+
+const BIN: &str = env!("CARGO_BIN_EXE_guessing_game");
+
+// Starts a server on a free port and returns it with the port it got
+fn start_server(args: &[&str]) -> (Child, u16)
+{
+    let mut server = Command::new(BIN)
+        .args(["serve", "--port", "0"])
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start server");
+
+    // first line: "listening on 127.0.0.1:PORT, waiting for N players"
+    let mut line = String::new();
+    BufReader::new(server.stdout.as_mut().unwrap()).read_line(&mut line).unwrap();
+    let port = line
+        .split(':')
+        .nth(1)
+        .and_then(|rest| rest.split(',').next())
+        .and_then(|port| port.parse().ok())
+        .unwrap_or_else(|| panic!("unexpected first line '{line}'"));
+
+    (server, port)
+}
+
+struct Client
+{
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client
+{
+    fn connect(port: u16) -> Client
+    {
+        let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        // a server that goes quiet fails the test instead of hanging it
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        Client { writer: stream.try_clone().unwrap(), reader: BufReader::new(stream) }
+    }
+
+    fn send(&mut self, line: &str)
+    {
+        writeln!(self.writer, "{line}").unwrap();
+    }
+
+    fn recv(&mut self) -> String
+    {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn expect(&mut self, expected: &str)
+    {
+        assert_eq!(self.recv(), expected);
+    }
+}
+
+// Two players joining a server that waits for two
+fn two_players(args: &[&str]) -> (Child, Client, Client)
+{
+    let (server, port) = start_server(args);
+
+    let mut alice = Client::connect(port);
+    alice.send("JOIN alice");
+    alice.expect("WELCOME alice 1 100 -");
+    alice.expect("JOINED alice 1 2");
+
+    let mut bob = Client::connect(port);
+    bob.send("JOIN bob");
+    bob.expect("WELCOME bob 1 100 -");
+
+    for client in [&mut alice, &mut bob]
+    {
+        client.expect("JOINED bob 2 2");
+        client.expect("START");
+        client.expect("TURN alice");
+    }
+
+    (server, alice, bob)
+}
+
+#[test]
+fn players_take_turns_until_someone_wins()
+{
+    let (mut server, mut alice, mut bob) = two_players(&["--players", "2", "--max", "100", "--seed", "1"]);
+
+    // both players binary search together, taking turns
+    let (mut low, mut high) = (1, 100);
+    let mut turn = 0;
+    let winner = loop {
+        let guess = low + (high - low) / 2;
+        let (name, other) = if turn % 2 == 0 { ("alice", "bob") } else { ("bob", "alice") };
+        let player = if turn % 2 == 0 { &mut alice } else { &mut bob };
+        player.send(&format!("GUESS {guess}"));
+
+        // everybody sees every result
+        let result = alice.recv();
+        assert_eq!(bob.recv(), result);
+        let verdict = result.rsplit(' ').next().unwrap().to_string();
+        assert_eq!(result, format!("RESULT {name} {guess} {verdict}"));
+
+        match verdict.as_str()
+        {
+            "LESS" => low = guess + 1,
+            "GREATER" => high = guess - 1,
+            "EQUAL" => break name,
+            _ => panic!("unexpected verdict in '{result}'"),
+        }
+
+        for client in [&mut alice, &mut bob]
+        {
+            client.expect(&format!("TURN {other}"));
+        }
+        turn += 1;
+    };
+
+    let attempts = turn / 2 + 1;
+    for client in [&mut alice, &mut bob]
+    {
+        client.expect(&format!("WINNER {winner} {attempts}"));
+    }
+
+    assert!(server.wait().unwrap().success());
+}
+
+#[test]
+fn out_of_turn_and_invalid_lines_are_rejected()
+{
+    let (mut server, mut alice, mut bob) = two_players(&["--players", "2", "--max", "100"]);
+
+    bob.send("GUESS 50");
+    bob.expect("ERROR not your turn");
+
+    alice.send("GUESS lots");
+    alice.expect("ERROR 'lots' is not a number");
+
+    alice.send("GUESS 1000");
+    alice.expect("ERROR the number is between 1 and 100");
+    // a client only guesses on its TURN, so it gets that again
+    alice.expect("TURN alice");
+
+    alice.send("DANCE");
+    alice.expect("ERROR unknown command 'DANCE'");
+
+    // still alice's turn after all of that, leaving passes the turn on
+    alice.send("QUIT");
+    bob.expect("LEFT alice");
+    bob.expect("TURN bob");
+
+    bob.send("QUIT");
+    assert!(server.wait().unwrap().success());
+}
+
+#[test]
+fn duplicate_names_get_a_free_number()
+{
+    let (mut server, port) = start_server(&["--players", "4", "--max", "100"]);
+
+    let mut clients = Vec::new();
+    for (name, given) in [("bob", "bob"), ("bob#2", "bob#2"), ("bob", "bob#3"), ("bob", "bob#4")]
+    {
+        let mut client = Client::connect(port);
+        client.send(&format!("JOIN {name}"));
+        client.expect(&format!("WELCOME {given} 1 100 -"));
+        clients.push(client);
+    }
+
+    server.kill().unwrap();
+    server.wait().unwrap();
+}
+
+#[test]
+fn late_joiners_are_turned_away()
+{
+    let (mut server, _alice, bob) = two_players(&["--players", "2", "--max", "100"]);
+    let port = bob.writer.peer_addr().unwrap().port();
+
+    let mut carol = Client::connect(port);
+    carol.expect("ERROR game already started");
+
+    server.kill().unwrap();
+    server.wait().unwrap();
+}
+
+#[test]
+fn nobody_wins_when_attempts_run_out()
+{
+    let (mut server, port) = start_server(&["--players", "1", "--min", "1", "--max", "100", "--attempts", "2", "--seed", "3"]);
+
+    let mut solo = Client::connect(port);
+    solo.send("JOIN solo");
+    solo.expect("WELCOME solo 1 100 2");
+    solo.expect("JOINED solo 1 1");
+    solo.expect("START");
+    solo.expect("TURN solo");
+
+    // with seed 3 the secret is neither 1 nor 100
+    solo.send("GUESS 1");
+    solo.expect("RESULT solo 1 LESS");
+    solo.expect("TURN solo");
+    solo.send("GUESS 100");
+    solo.expect("RESULT solo 100 GREATER");
+
+    let last = solo.recv();
+    assert!(last.starts_with("NOWINNER "), "{last}");
+    assert!(server.wait().unwrap().success());
+}
+
+#[test]
+fn the_server_stops_listening_after_the_game()
+{
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = GameConfig { min: 1, max: 1, ..GameConfig::default() };
+    let server = thread::spawn(move || serve(listener, &config, 1).unwrap());
+
+    let mut solo = Client::connect(port);
+    solo.send("JOIN solo");
+    solo.expect("WELCOME solo 1 1 -");
+    solo.expect("JOINED solo 1 1");
+    solo.expect("START");
+    solo.expect("TURN solo");
+    solo.send("GUESS 1");
+    solo.expect("RESULT solo 1 EQUAL");
+    solo.expect("WINNER solo 1");
+
+    assert_eq!(server.join().unwrap(), Some((String::from("solo"), 1)));
+    // the accept thread is gone and took the listener with it
+    assert!(TcpStream::connect(("127.0.0.1", port)).is_err());
+}
+
+#[test]
+fn empty_names_are_rejected_before_connecting()
+{
+    let output = Command::new(BIN).args(["join", "--name", "   ", "--addr", "127.0.0.1:1"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "guessing game: the name can't be empty\n");
+}
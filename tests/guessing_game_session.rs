@@ -0,0 +1,135 @@
+// Records guessing games to session logs and replays them, including logs
+// that were edited after the game.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{self, Command, Output};
+
+const BIN: &str = env!("CARGO_BIN_EXE_guessing_game");
+
+fn game(args: &[&str]) -> Output
+{
+    Command::new(BIN).args(args).output().expect("failed to run the game")
+}
+
+fn stdout(output: &Output) -> String
+{
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String
+{
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+// A seeded bot game recorded to a fresh log, with what it printed
+fn recorded_game(case: &str) -> (PathBuf, String)
+{
+    let log = env::temp_dir().join(format!("guessing-game-session-{}-{case}.log", process::id()));
+    let output = game(&["--bot", "binary", "--min", "1", "--max", "100", "--seed", "9", "--record", log.to_str().unwrap()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    (log, stdout(&output))
+}
+
+fn edit(log: &PathBuf, from: &str, to: &str)
+{
+    let text = fs::read_to_string(log).unwrap();
+    assert!(text.contains(from), "'{from}' not in\n{text}");
+    fs::write(log, text.replacen(from, to, 1)).unwrap();
+}
+
+#[test]
+fn replay_shows_the_recorded_game()
+{
+    let (log, played) = recorded_game("replay");
+
+    let replayed = game(&["replay", log.to_str().unwrap(), "--instant"]);
+
+    assert!(replayed.status.success(), "{}", stderr(&replayed));
+    assert_eq!(stdout(&replayed), format!("Replaying binary's game\n{played}"));
+    fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn edited_feedback_is_detected()
+{
+    let (log, _) = recorded_game("feedback");
+    let text = fs::read_to_string(&log).unwrap();
+    let first_guess = text.lines().find(|line| line.starts_with("guess\t")).unwrap().to_string();
+    let flipped = if first_guess.ends_with("LESS") { first_guess.replace("LESS", "GREATER") } else { first_guess.replace("GREATER", "LESS") };
+    edit(&log, &first_guess, &flipped);
+
+    let replayed = game(&["replay", log.to_str().unwrap(), "--instant"]);
+
+    assert_eq!(replayed.status.code(), Some(1));
+    assert_eq!(stdout(&replayed), "");
+    assert!(stderr(&replayed).contains("has been tampered with"));
+    assert!(stderr(&replayed).contains("guess 1: 50 was answered"), "{}", stderr(&replayed));
+    fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn secret_must_match_the_seed()
+{
+    let (log, _) = recorded_game("secret");
+    let text = fs::read_to_string(&log).unwrap();
+    let secret = text.lines().find(|line| line.starts_with("secret\t")).unwrap().to_string();
+    let winning = text.lines().find(|line| line.ends_with("EQUAL")).unwrap().to_string();
+
+    // the winning guess is forged along with the secret
+    let forged: u32 = if secret == "secret\t1" { 2 } else { 1 };
+    let at = winning.split('\t').nth(1).unwrap();
+    edit(&log, &secret, &format!("secret\t{forged}"));
+    edit(&log, &winning, &format!("guess\t{at}\t{forged}\tEQUAL"));
+
+    let replayed = game(&["replay", log.to_str().unwrap(), "--instant"]);
+
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(stderr(&replayed).contains(&format!("secret {forged} doesn't match the seed")), "{}", stderr(&replayed));
+    fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn malformed_logs_are_rejected()
+{
+    let (log, _) = recorded_game("malformed");
+    edit(&log, "end\t", "fin\t");
+
+    let replayed = game(&["replay", log.to_str().unwrap()]);
+
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(stderr(&replayed).contains("unexpected line"), "{}", stderr(&replayed));
+    fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn times_too_long_for_a_duration_are_rejected()
+{
+    let (log, _) = recorded_game("duration");
+    let text = fs::read_to_string(&log).unwrap();
+    let first_guess = text.lines().find(|line| line.starts_with("guess\t")).unwrap().to_string();
+    let at = first_guess.split('\t').nth(1).unwrap();
+    edit(&log, &first_guess, &first_guess.replacen(at, "1e30", 1));
+
+    let replayed = game(&["replay", log.to_str().unwrap(), "--instant"]);
+
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(stderr(&replayed).contains("'1e30' is not a time in seconds"), "{}", stderr(&replayed));
+    fs::remove_file(log).unwrap();
+}
+
+#[test]
+fn tiny_speeds_are_rejected()
+{
+    let (log, _) = recorded_game("speed");
+
+    for speed in ["1e-300", "0", "-1", "0.0001"]
+    {
+        let replayed = game(&["replay", log.to_str().unwrap(), "--speed", speed]);
+        assert_eq!(replayed.status.code(), Some(2));
+        assert_eq!(stderr(&replayed), "guessing game: option '--speed' needs a number of at least 0.001\n");
+    }
+    fs::remove_file(log).unwrap();
+}
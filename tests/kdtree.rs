@@ -0,0 +1,99 @@
+// The k-d tree, checked against looking at every point
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{Chebyshev, Euclidean, KdPoint, KdTree, Manhattan, Metric, Neighbour, Point, Point3};
+
+// (distance, index) of every point, closest first, ties by index
+fn brute_force<P: KdPoint, M: Metric>(points: &[P], query: &P, metric: &M) -> Vec<(f64, usize)>
+{
+    let mut all: Vec<(f64, usize)> = points.iter().enumerate().map(|(i, p)| (metric.distance(query, p), i)).collect();
+    all.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    all
+}
+
+fn found<P>(neighbours: &[Neighbour<P>]) -> Vec<(f64, usize)>
+{
+    neighbours.iter().map(|n| (n.distance, n.index)).collect()
+}
+
+fn compare<P: KdPoint, M: Metric>(points: &[P], queries: &[P], metric: &M, radius: f64)
+{
+    let tree = KdTree::build(points);
+    assert_eq!(tree.len(), points.len());
+
+    for query in queries
+    {
+        let expected = brute_force(points, query, metric);
+
+        for k in [1, 5, 17]
+        {
+            let expected: Vec<(f64, usize)> = expected.iter().copied().take(k).collect();
+            assert_eq!(found(&tree.k_nearest(query, k, metric)), expected);
+        }
+        assert_eq!(tree.nearest(query, metric).map(|n| (n.distance, n.index)), expected.first().copied());
+
+        let within: Vec<(f64, usize)> = expected.iter().copied().filter(|&(d, _)| d <= radius).collect();
+        assert_eq!(found(&tree.within_radius(query, radius, metric)), within);
+    }
+}
+
+#[test]
+fn matches_brute_force_in_2d()
+{
+    let mut rng = StdRng::seed_from_u64(48);
+
+    for round in 0..30
+    {
+        let count = rng.gen_range(0..400);
+        // small spread on some rounds, so there are plenty of ties
+        let spread = if round % 3 == 0 { 8 } else { 1000 };
+        let points: Vec<Point<i64>> = (0..count).map(|_| Point::new(rng.gen_range(-spread..spread), rng.gen_range(-spread..spread))).collect();
+        let queries: Vec<Point<i64>> = (0..20).map(|_| Point::new(rng.gen_range(-spread..spread), rng.gen_range(-spread..spread))).collect();
+        let radius = spread as f64 / 5.0;
+
+        compare(&points, &queries, &Euclidean, radius);
+        compare(&points, &queries, &Manhattan, radius);
+        compare(&points, &queries, &Chebyshev, radius);
+    }
+}
+
+#[test]
+fn matches_brute_force_in_3d()
+{
+    let mut rng = StdRng::seed_from_u64(3);
+    let random = |rng: &mut StdRng| Point3::new(rng.r#gen::<f64>() * 100.0, rng.r#gen::<f64>() * 100.0, rng.r#gen::<f64>() * 100.0);
+
+    for _ in 0..20
+    {
+        let count = rng.gen_range(1..500);
+        let points: Vec<Point3<f64>> = (0..count).map(|_| random(&mut rng)).collect();
+        let queries: Vec<Point3<f64>> = (0..20).map(|_| random(&mut rng)).collect();
+
+        compare(&points, &queries, &Euclidean, 15.0);
+        compare(&points, &queries, &Manhattan, 15.0);
+        compare(&points, &queries, &Chebyshev, 15.0);
+    }
+}
+
+#[test]
+fn metrics_and_edge_cases()
+{
+    let (a, b) = (Point::new(0, 0), Point::new(3, -4));
+    assert_eq!(Euclidean.distance(&a, &b), b.dist_from_origin());
+    assert_eq!(Manhattan.distance(&a, &b), 7.0);
+    assert_eq!(Chebyshev.distance(&a, &b), 4.0);
+    assert_eq!(Euclidean.distance(&Point3::new(1, 2, 3), &Point3::new(3, 5, 9)), 7.0);
+
+    let empty: KdTree<Point<i32>> = KdTree::build(&[]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.nearest(&a, &Euclidean), None);
+    assert!(empty.within_radius(&a, 10.0, &Euclidean).is_empty());
+
+    // the same point twice, both found, in the order given
+    let tree = KdTree::build(&[b, a, b]);
+    assert_eq!(found(&tree.k_nearest(&b, 10, &Euclidean)), vec![(0.0, 0), (0.0, 2), (5.0, 1)]);
+    assert!(tree.k_nearest(&b, 0, &Euclidean).is_empty());
+    // the radius is inclusive
+    assert_eq!(found(&tree.within_radius(&a, 5.0, &Euclidean)), vec![(0.0, 1), (5.0, 0), (5.0, 2)]);
+}
@@ -0,0 +1,111 @@
+// The reusable types, used from outside of the crate like any other library
+
+use rust_progr_lang_course::generics::notify;
+use rust_progr_lang_course::guessing_game::{play_with, BinarySearchBot, GameConfig, Silent};
+use rust_progr_lang_course::{
+    search,
+    search_case_insensitive,
+    Coin,
+    Inventory,
+    List,
+    MyBox,
+    NewsArticle,
+    Rectangle,
+    ShirtColor,
+    SocialPost,
+    Summary,
+    UsState,
+};
+
+#[test]
+fn rectangles()
+{
+    let big = Rectangle::new(30, 50);
+
+    assert_eq!(big.area(), 1500);
+    assert!(big.can_hold(&Rectangle::new(10, 40)));
+    assert!(!big.can_hold(&Rectangle::new(60, 45)));
+    // strictly bigger, like in the book
+    assert!(!big.can_hold(&Rectangle::new(30, 10)));
+    assert!(!big.can_hold(&big));
+    assert_eq!(Rectangle::new(10, 40).checked_area_diff(&big), None);
+    // areas past u32 don't overflow
+    let huge = Rectangle::new(u32::MAX, u32::MAX);
+    assert_eq!(huge.checked_area_diff(&big), Some(u64::from(u32::MAX) * u64::from(u32::MAX) - 1500));
+    assert_eq!(Rectangle::square(3), Rectangle { width: 3, height: 3, ..Rectangle::default() });
+}
+
+#[test]
+fn coins()
+{
+    let coins = [Coin::Penny, Coin::Nickel, Coin::Dime, Coin::Quarter(UsState::Alaska)];
+
+    assert_eq!(coins.iter().map(|c| u32::from(c.value_in_cents())).sum::<u32>(), 41);
+    assert!(UsState::Alabama.existed_in(1900));
+    assert!(!UsState::Alaska.existed_in(1900));
+}
+
+#[test]
+fn shirt_giveaway()
+{
+    let store = Inventory { shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue] };
+
+    assert_eq!(store.giveaway(Some(ShirtColor::Red)), ShirtColor::Red);
+    assert_eq!(store.giveaway(None), ShirtColor::Blue);
+}
+
+#[test]
+fn summaries()
+{
+    let article = NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    };
+    let post = SocialPost {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        repost: false,
+    };
+
+    assert_eq!(article.summarize(), "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)");
+    assert_eq!(post.summarize_author(), "SocialPost auth: @horse_ebooks");
+    assert_eq!(notify(&post), "Breaking news! horse_ebooks: of course, as you probably already know, people");
+}
+
+#[test]
+fn cons_list_and_my_box()
+{
+    let list = List::from_slice(&[1, 2, 3]);
+
+    assert_eq!(list, List::Cons(1, Box::new(List::Cons(2, Box::new(List::Cons(3, Box::new(List::Nil)))))));
+    assert_eq!(list.iter().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!((list.len(), list.sum()), (3, 6));
+    assert!(List::Nil.is_empty());
+
+    let name = MyBox::new(String::from("Rust"));
+    // deref coercion: &MyBox<String> -> &String -> &str
+    let greet = |name: &str| format!("Hello, {name}!");
+    assert_eq!(greet(&name), "Hello, Rust!");
+}
+
+#[test]
+fn minigrep_search()
+{
+    let contents = "Rust:\nsafe, fast, productive.\nPick three.\nTrust me.";
+
+    assert_eq!(search("duct", contents), ["safe, fast, productive."]);
+    assert_eq!(search_case_insensitive("rUsT", contents), ["Rust:", "Trust me."]);
+}
+
+#[test]
+fn guessing_game_without_a_terminal()
+{
+    let config = GameConfig { seed: Some(7), ..GameConfig::default() };
+    let result = play_with(&config, &mut BinarySearchBot::new(), &mut Silent);
+
+    assert!(result.won);
+    assert!(result.attempts <= config.par());
+}
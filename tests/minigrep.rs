@@ -0,0 +1,267 @@
+// Golden-file tests for the minigrep binary.
+//
+// Every case runs the binary inside a freshly built fixture tree and compares
+// its exit code, stdout and stderr with tests/golden/minigrep/<case>.txt.
+// After an intended change of output, regenerate the golden files with:
+//
+//     MINIGREP_BLESS=1 cargo test --test minigrep
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::Duration;
+
+const BIN: &str = env!("CARGO_BIN_EXE_minigrep");
+
+// The poem used in the book's minigrep tests
+const POEM: &str = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Trust me.
+";
+
+const DICKINSON: &str = "\
+I'm nobody! Who are you?
+Are you nobody, too?
+Then there's a pair of us - don't tell!
+They'd banish us, you know.
+
+How dreary to be somebody!
+How public, like a frog
+To tell your name the livelong day
+To an admiring bog!
+";
+
+// Builds a small project-like tree in a fresh temporary directory
+fn fixture(case: &str) -> PathBuf
+{
+    let root = env::temp_dir().join(format!("minigrep-golden-{}-{case}", process::id()));
+    if root.exists()
+    {
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    let files: &[(&str, &[u8])] = &[
+        ("poem.txt", POEM.as_bytes()),
+        ("dickinson.txt", DICKINSON.as_bytes()),
+        ("Cargo.toml", b"[package]\nname = \"fixture\"\nedition = \"2024\"\n"),
+        ("notes.md", b"# Notes\nRust is fast.\nrust is also safe.\n"),
+        ("src/main.rs", b"fn main() {\n    println!(\"safe and fast\");\n}\n"),
+        ("src/lib.rs", b"pub fn fast() -> bool {\n    true // fast, fast, fast\n}\n"),
+        ("logs/run.log", b"INFO started\nWARN slow disk\nINFO fast path taken\n"),
+        ("data/config.json", b"{ \"fast\": true }\n"),
+        ("data/blob.bin", b"fast\xff\xfe\x00binary"),
+        (".hidden/secret.txt", b"fast but hidden\n"),
+        ("weird name:1.txt", b"fast with a colon in the file name\n"),
+    ];
+
+    for (path, contents) in files
+    {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    root
+}
+
+// --stats timings change from run to run, the golden files show them as N
+fn without_timings(text: &str) -> String
+{
+    text.split_inclusive('\n')
+        .map(|line| match line.split_once(' ')
+        {
+            Some((_, rest)) if rest.starts_with("seconds elapsed") || rest.starts_with("MiB/s throughput") => format!("N {rest}"),
+            _ => line.to_string(),
+        })
+        .collect()
+}
+
+// Renders a run in the golden file format, NUL bytes are shown as \0
+fn render(args: &[&str], output: &process::Output) -> String
+{
+    let text = |bytes: &[u8]| without_timings(&String::from_utf8_lossy(bytes)).replace('\0', "\\0");
+
+    format!(
+        "args: {}\nexit: {}\n--- stdout\n{}--- stderr\n{}",
+        args.join(" "),
+        output.status.code().map_or(String::from("signal"), |c| c.to_string()),
+        text(&output.stdout),
+        text(&output.stderr),
+    )
+}
+
+fn golden_path(case: &str) -> PathBuf
+{
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/minigrep")
+        .join(format!("{case}.txt"))
+}
+
+fn check(case: &str, args: &[&str], envs: &[(&str, &str)])
+{
+    let root = fixture(case);
+
+    let mut cmd = Command::new(BIN);
+    cmd.args(args).current_dir(&root).env_remove("IGNORE_CASE");
+    for (key, value) in envs
+    {
+        cmd.env(key, value);
+    }
+    let output = cmd.output().expect("failed to run minigrep");
+    fs::remove_dir_all(&root).unwrap();
+
+    let actual = render(args, &output);
+    let golden = golden_path(case);
+
+    if env::var_os("MINIGREP_BLESS").is_some()
+    {
+        fs::create_dir_all(golden.parent().unwrap()).unwrap();
+        fs::write(&golden, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden).unwrap_or_else(|_| {
+        panic!("missing {}, run with MINIGREP_BLESS=1 to create it", golden.display())
+    });
+
+    assert!(
+        expected == actual,
+        "output of case '{case}' differs from {}\n--- expected\n{expected}\n--- actual\n{actual}",
+        golden.display()
+    );
+}
+
+macro_rules! golden {
+    ($($case:ident: [$($arg:expr),*];)*) => {
+        $(
+            #[test]
+            fn $case()
+            {
+                check(stringify!($case), &[$($arg),*], &[]);
+            }
+        )*
+    };
+}
+
+golden! {
+    single_file: ["duct", "poem.txt"];
+    single_file_no_match: ["monomorphization", "poem.txt"];
+    case_sensitive: ["rUsT", "poem.txt"];
+    ignore_case_short: ["-i", "rUsT", "poem.txt"];
+    ignore_case_long: ["--ignore-case", "RUST", "poem.txt"];
+    multiple_files: ["to", "poem.txt", "dickinson.txt"];
+    recursive_current_dir: ["fast"];
+    recursive_subdir: ["fast", "src"];
+    type_rust: ["-t", "rust", "fast"];
+    type_long_equals: ["--type=md", "-i", "rust"];
+    type_several: ["-t", "rust", "-t", "json", "fast"];
+    type_not: ["-T", "txt", "-T", "rust", "fast"];
+    type_add: ["--type-add", "notes:*.md", "-t", "notes", "safe"];
+    type_add_after_select: ["-t", "logs", "--type-add", "logs:*.log", "INFO"];
+    type_unknown: ["-t", "cobol", "fast"];
+    type_add_invalid: ["--type-add", "nocolon", "fast"];
+    type_list: ["--type-list"];
+    explicit_file_ignores_type: ["-t", "rust", "Rust", "poem.txt"];
+    files_mode: ["--files"];
+    files_mode_typed: ["--files", "-t", "txt"];
+    files_mode_null: ["--files", "--null", "src"];
+    null_short: ["-0", "fast", "src", "weird name:1.txt"];
+    vimgrep: ["--vimgrep", "fast", "src"];
+    vimgrep_ignore_case: ["--vimgrep", "-i", "rust", "notes.md", "poem.txt"];
+    vimgrep_null: ["--vimgrep", "-0", "fast", "weird name:1.txt"];
+    missing_query: [];
+    missing_value: ["-t"];
+    unknown_flag: ["--ignore-cse", "rust"];
+    missing_file: ["rust", "no_such_file.txt"];
+    unknown_flag_single_dash: ["-ignore-case", "rust"];
+    unknown_flag_no_suggestion: ["--frobnicate", "rust"];
+    unknown_short_flag: ["-x", "rust"];
+    suggest_vimgrep: ["--vimgerp", "rust"];
+    help_long: ["--help"];
+    help_wins_over_errors: ["--bogus", "-h"];
+    version: ["--version"];
+    version_short: ["-V"];
+    help_flag_as_type_value: ["-t", "-h", "fast"];
+    version_flag_as_type_add_value: ["--type-add", "-V", "fast"];
+    query_after_double_dash: ["--", "- don't", "dickinson.txt"];
+    help_after_double_dash: ["-i", "--", "fast", "-h"];
+    stats: ["--stats", "fast", "src", "poem.txt"];
+    stats_no_match: ["--stats", "monomorphization", "poem.txt"];
+    stats_files_mode: ["--files", "--stats", "src"];
+}
+
+// The counts behind --stats, from the library
+#[test]
+fn run_counts_what_it_searched()
+{
+    use rust_progr_lang_course::minigrep::{run, Config};
+
+    let root = fixture("run_counts");
+    let config = |args: &[&str]| {
+        let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        args.push(root.join("src").display().to_string());
+        args.push(root.join("poem.txt").display().to_string());
+        Config::build(&args).unwrap()
+    };
+
+    let stats = run(&config(&["fast"])).unwrap();
+    let bytes = ["src/main.rs", "src/lib.rs", "poem.txt"].iter().map(|f| fs::metadata(root.join(f)).unwrap().len()).sum::<u64>();
+    assert_eq!((stats.files_scanned, stats.files_matched, stats.lines_matched, stats.files_unreadable), (3, 3, 4, 0));
+    assert_eq!(stats.bytes_searched, bytes);
+    assert!(stats.elapsed > Duration::ZERO);
+
+    let stats = run(&config(&["-i", "RUST"])).unwrap();
+    assert_eq!((stats.files_scanned, stats.files_matched, stats.lines_matched), (3, 1, 2));
+
+    // listing isn't searching, but it's timed all the same
+    for listing in [&["--files"][..], &["--type-list"][..]]
+    {
+        let stats = run(&config(listing)).unwrap();
+        assert_eq!((stats.files_scanned, stats.bytes_searched, stats.lines_matched), (0, 0, 0));
+        assert!(stats.elapsed > Duration::ZERO, "{listing:?}");
+    }
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn ignore_case_env_var()
+{
+    check("ignore_case_env_var", &["rUsT", "poem.txt"], &[("IGNORE_CASE", "1")]);
+}
+
+// A file that can't be read is reported and the search goes on; a link to
+// a directory above would make the walk go round forever if it was followed
+#[cfg(unix)]
+#[test]
+fn unreadable_files_and_symlink_loops()
+{
+    use std::os::unix::fs::symlink;
+
+    let root = env::temp_dir().join(format!("minigrep-links-{}", process::id()));
+    if root.exists()
+    {
+        fs::remove_dir_all(&root).unwrap();
+    }
+    fs::create_dir_all(root.join("sub")).unwrap();
+    fs::write(root.join("a.txt"), "fast a\n").unwrap();
+    fs::write(root.join("sub/c.txt"), "fast c\n").unwrap();
+    // a dangling link can't be read, not even by root
+    symlink(root.join("missing.txt"), root.join("b.txt")).unwrap();
+    symlink(&root, root.join("sub/loop")).unwrap();
+    symlink(root.join("a.txt"), root.join("sub/link.txt")).unwrap();
+
+    let output = Command::new(BIN).args(["fast"]).current_dir(&root).env_remove("IGNORE_CASE").output().unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout, "./a.txt:fast a\n./sub/c.txt:fast c\n./sub/link.txt:fast a\n");
+    assert!(stderr.starts_with("minigrep: ./b.txt: No such file or directory"), "{stderr}");
+    assert_eq!(stderr.lines().count(), 1);
+    assert_eq!(output.status.code(), Some(4));
+}
@@ -0,0 +1,107 @@
+// Packings must be valid for every heuristic: nothing overlaps, nothing
+// sticks out of its bin and every rectangle is accounted for
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{pack, Heuristic, PackConfig, Packing};
+use rust_progr_lang_course::Rectangle;
+
+fn configs() -> impl Iterator<Item = PackConfig>
+{
+    Heuristic::ALL.into_iter().flat_map(|heuristic| {
+        [false, true].map(|allow_rotation| PackConfig { heuristic, bin_width: 256, bin_height: 128, allow_rotation })
+    })
+}
+
+fn assert_valid(rects: &[Rectangle], packing: &Packing)
+{
+    let config = &packing.config;
+    let bin = Rectangle::new(config.bin_width, config.bin_height);
+
+    let mut seen = vec![false; rects.len()];
+    for &i in packing.placements.iter().map(|p| &p.index).chain(&packing.unplaced)
+    {
+        assert!(!seen[i], "{config:?}: rectangle {i} is placed twice");
+        seen[i] = true;
+    }
+    assert!(seen.iter().all(|&s| s), "{config:?}: rectangles went missing");
+
+    for p in &packing.placements
+    {
+        let wanted = rects[p.index];
+        let size = if p.rotated { (wanted.height, wanted.width) } else { (wanted.width, wanted.height) };
+        assert!(!p.rotated || config.allow_rotation, "{config:?}: {p:?} rotated");
+        assert_eq!((p.rect.width, p.rect.height), size, "{config:?}: {p:?}");
+        assert!(p.bin < packing.bins && bin.contains_rect(&p.rect), "{config:?}: {p:?} outside of its bin");
+
+        let overlapping = packing.placements_in(p.bin).find(|other| other.index != p.index && other.rect.intersects(&p.rect));
+        assert_eq!(overlapping, None, "{config:?}: {p:?} overlaps");
+    }
+
+    for &i in &packing.unplaced
+    {
+        let r = rects[i];
+        let fits = |w, h| w <= config.bin_width && h <= config.bin_height;
+        let fits_somehow = fits(r.width, r.height) || (config.allow_rotation && fits(r.height, r.width));
+        assert!(!fits_somehow, "{config:?}: {r:?} fits but wasn't placed");
+    }
+    assert!((0.0..=1.0).contains(&packing.utilisation()));
+}
+
+#[test]
+fn random_lists_pack_validly()
+{
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..30
+    {
+        let rects: Vec<Rectangle> = (0..rng.gen_range(0..120))
+            .map(|_| Rectangle::new(rng.gen_range(0..100), rng.gen_range(0..300)))
+            .collect();
+
+        for config in configs()
+        {
+            assert_valid(&rects, &pack(&rects, &config));
+        }
+    }
+}
+
+#[test]
+fn a_perfect_fit_fills_one_bin()
+{
+    let squares = vec![Rectangle::square(64); 8];
+
+    for config in configs()
+    {
+        let packing = pack(&squares, &config);
+        assert_valid(&squares, &packing);
+        assert_eq!(packing.bins, 1, "{config:?}");
+        assert_eq!(packing.utilisation(), 1.0, "{config:?}");
+    }
+}
+
+#[test]
+fn rotation_lets_tall_rectangles_in()
+{
+    let tall = [Rectangle::new(100, 200)];
+
+    let upright = pack(&tall, &PackConfig { bin_width: 256, bin_height: 128, ..PackConfig::default() });
+    let rotated = pack(&tall, &PackConfig { bin_width: 256, bin_height: 128, allow_rotation: true, ..PackConfig::default() });
+
+    assert_eq!((upright.bins, upright.unplaced.as_slice()), (0, &[0][..]));
+    assert_eq!(rotated.bins, 1);
+    assert!(rotated.placements[0].rotated);
+}
+
+#[test]
+fn maxrects_packs_at_least_as_tight_as_shelves()
+{
+    let mut rng = StdRng::seed_from_u64(11);
+    let rects: Vec<Rectangle> = (0..500).map(|_| Rectangle::new(rng.gen_range(4..64), rng.gen_range(4..64))).collect();
+
+    let bins = |heuristic| pack(&rects, &PackConfig { heuristic, allow_rotation: true, ..PackConfig::default() }).bins;
+
+    assert!(bins(Heuristic::MaxRects) <= bins(Heuristic::Shelf));
+    assert_eq!("guillotine".parse(), Ok(Heuristic::Guillotine));
+    assert!("tetris".parse::<Heuristic>().is_err());
+}
@@ -0,0 +1,131 @@
+// Points and generic rectangles behave the same for every Scalar
+
+use rust_progr_lang_course::geometry::{Point, Rect, Scalar};
+use rust_progr_lang_course::Rectangle;
+
+// Small whole numbers, which every Scalar holds exactly
+fn n<T: Scalar>(value: u8) -> T
+{
+    T::from_f64(f64::from(value)).unwrap()
+}
+
+fn p<T: Scalar>(x: u8, y: u8) -> Point<T>
+{
+    Point::new(n(x), n(y))
+}
+
+fn vector_maths<T: Scalar>()
+{
+    let (a, b) = (p::<T>(3, 4), p::<T>(1, 2));
+
+    assert_eq!(a + b, p(4, 6));
+    assert_eq!(a - b, p(2, 2));
+    assert_eq!(a * n(3), p(9, 12));
+    assert_eq!(a.dot(b), 11.0);
+    assert_eq!(a.cross(p(6, 9)), 3.0);
+    assert_eq!(p::<T>(6, 9).cross(a), -3.0);
+    assert_eq!(b.cross(b * n(2)), 0.0);
+    assert_eq!(a.dist_from_origin(), 5.0);
+    assert_eq!(b.distance(a), a.distance(b));
+    assert_eq!(a.distance_squared(b), 8.0);
+    assert_eq!((*a.x(), *a.y()), (n(3), n(4)));
+    assert_eq!(Point::from((n(3), n(4))), a);
+}
+
+fn rect_maths<T: Scalar>()
+{
+    let r = Rect::from_origin_size(p::<T>(2, 2), p(4, 6));
+
+    assert_eq!((r.width, r.height, r.area()), (n(4), n(6), n(24)));
+    assert_eq!(r.end(), p(6, 8));
+    assert_eq!(r.center(), Point::new(4.0, 5.0));
+    assert_eq!(Rect::from_corners(p::<T>(6, 2), p(2, 8)), r);
+    assert_eq!(r.translate(p(1, 1)).origin(), p(3, 3));
+    assert_eq!(r.scale(n(2)).size(), p(8, 12));
+    assert!(r.contains_point(p(2, 2)) && !r.contains_point(p(6, 2)));
+    assert_eq!(r.intersection(&Rect::from_origin_size(p(5, 0), p(10, 3))), Some(Rect::from_origin_size(p(5, 2), p(1, 1))));
+    assert!(!r.intersects(&Rect::from_origin_size(p(6, 2), p(1, 1))));
+    assert_eq!(r.union_bounds(&Rect::from_origin_size(p(0, 9), p(1, 1))), Rect::from_origin_size(p(0, 2), p(6, 8)));
+}
+
+#[test]
+fn every_scalar_does_the_same_maths()
+{
+    vector_maths::<u32>();
+    vector_maths::<i32>();
+    vector_maths::<i64>();
+    vector_maths::<f32>();
+    vector_maths::<f64>();
+
+    rect_maths::<u32>();
+    rect_maths::<i32>();
+    rect_maths::<i64>();
+    rect_maths::<f32>();
+    rect_maths::<f64>();
+}
+
+#[test]
+fn products_do_not_overflow()
+{
+    // clockwise u32 vectors have a negative cross product
+    assert_eq!(Point::new(0u32, 1).cross(Point::new(1, 0)), -1.0);
+    assert_eq!(Point::new(i32::MAX, 0).dot(Point::new(i32::MAX, 0)), f64::from(i32::MAX) * f64::from(i32::MAX));
+    assert_eq!(Point::new(i32::MIN, 0).cross(Point::new(0, i32::MIN)), 2f64.powi(62));
+}
+
+#[test]
+fn casts_check_the_range()
+{
+    assert_eq!(Point::new(1.4f64, 2.6).cast::<i32>(), Some(Point::new(1, 3)));
+    assert_eq!(Point::new(-1i64, 0).cast::<u32>(), None);
+    assert_eq!(Point::new(f64::NAN, 0.0).cast::<f32>(), None);
+    assert_eq!(Point::new(1e300, 0.0).cast::<f32>(), None);
+    assert_eq!(Point::new(3u32, 4).cast::<i64>(), Some(Point::new(3, 4)));
+    assert_eq!(Point::new(i64::from(u32::MAX) + 1, 0).cast::<u32>(), None);
+    assert_eq!(Point::new(u32::MAX, 0).cast::<u32>(), Some(Point::new(u32::MAX, 0)));
+    assert_eq!(Point::new(3u32, 4).cast::<f32>(), Some(Point::new(3.0, 4.0)));
+}
+
+#[test]
+fn integer_casts_are_exact()
+{
+    // none of these survive a trip through f64
+    for value in [i64::MIN, i64::MIN + 1, -(1 << 53) - 1, (1 << 53) + 1, i64::MAX - 1, i64::MAX]
+    {
+        let p = Point::new(value, -value.saturating_add(1));
+        assert_eq!(p.cast::<i64>(), Some(p));
+    }
+    assert_eq!(Point::new(i64::MAX, 0).cast::<i32>(), None);
+    assert_eq!(Point::new(i64::from(i32::MIN), i64::from(u32::MAX)).cast::<i32>(), None);
+    assert_eq!(Point::new(i64::from(i32::MIN), 0).cast::<i32>(), Some(Point::new(i32::MIN, 0)));
+    assert_eq!(Point::new(u32::MAX, 0).cast::<i64>().and_then(|p| p.cast::<u32>()), Some(Point::new(u32::MAX, 0)));
+    assert_eq!(i64::MAX.cast::<f64>(), Some(9.223372036854776e18));
+}
+
+#[test]
+fn rectangles_convert_both_ways()
+{
+    let cells = Rectangle::at(-5, 10, 30, 50);
+    let wide: Rect<i64> = cells.into();
+
+    assert_eq!(wide, Rect::from_origin_size(Point::new(-5, 10), Point::new(30, 50)));
+    assert_eq!(Rectangle::try_from(wide), Ok(cells));
+    assert_eq!(Rect::<f64>::from(cells).center(), Point::new(10.0, 35.0));
+    assert_eq!(cells.origin(), Point::new(-5, 10));
+    assert!(<Rectangle>::try_from(Rect::from_origin_size(Point::new(0, 0), Point::new(-1, 5))).is_err());
+    assert!(<Rectangle>::try_from(Rect::from_origin_size(Point::new(i64::from(i32::MAX) + 1, 0), Point::new(1, 1))).is_err());
+}
+
+#[test]
+fn one_rectangle_type_for_every_scalar()
+{
+    let cells = Rectangle::at(-5, 10, 30, 50);
+    assert_eq!((cells.center(), cells.size()), (Point::new(10.0, 35.0), Point::new(30, 50)));
+
+    let floats = Rect::from_origin_size(Point::new(0.5, 1.0), Point::new(2.0, 3.0));
+    assert_eq!(floats, Rectangle { x: 0.5, y: 1.0, width: 2.0, height: 3.0 });
+
+    // position and sides can differ, like the i32 and u32 of plain Rectangle
+    let mixed: Rectangle<i64, f32> = Rectangle { x: -2, y: 0, width: 4.0, height: 1.0 };
+    assert_eq!(mixed.center(), Point::new(0.0, 0.5));
+}
@@ -0,0 +1,87 @@
+// What the renderers draw for small scenes
+
+use rust_progr_lang_course::geometry::{pack, PackConfig, Scene};
+use rust_progr_lang_course::Rectangle;
+
+fn overlapping() -> Scene
+{
+    let mut scene = Scene::new();
+    scene.add(Rectangle::at(0, 0, 30, 20), "big");
+    scene.add(Rectangle::at(20, 10, 20, 10), "<overlap>").color = Some(String::from("red"));
+    scene.add(Rectangle::at(45, 0, 1, 1), "");
+    scene
+}
+
+#[test]
+fn ascii_draws_outlines_and_a_legend()
+{
+    let expected = "\
++-------------+       C
+|AAAAAAAAAAAAA|
+|AAAAAAAAA+--------+
+|AAAAAAAAA|BBBBBBBB|
++---------+--------+
+1 column = 2, 1 row = 4
+A  30x20 at (0, 0)  big
+B  20x10 at (20, 10)  <overlap>
+C  1x1 at (45, 0)
+";
+
+    assert_eq!(overlapping().to_ascii(40, 20), expected);
+}
+
+#[test]
+fn ascii_scales_down_to_fit()
+{
+    let drawing = overlapping().to_ascii(10, 5);
+    let canvas: Vec<&str> = drawing.lines().take_while(|line| !line.starts_with("1 column")).collect();
+
+    assert_eq!(canvas, ["AAAAAA   C", "AAAABBBB"]);
+    assert!(drawing.contains("1 column = 5, 1 row = 10"));
+    assert_eq!(Scene::new().to_ascii(10, 5), "(nothing to draw)\n");
+}
+
+#[test]
+fn svg_has_every_shape_with_escaped_labels()
+{
+    let mut scene = overlapping();
+    scene.grid = Some(10);
+    let svg = scene.to_svg();
+
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.92 -0.92 47.84 21.84""#), "{svg}");
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains(r#"<pattern id="grid" x="0" y="0" width="10" height="10""#));
+    assert_eq!(svg.matches(r#"fill-opacity="0.6""#).count(), 3);
+    assert!(svg.contains(r##"<rect x="0" y="0" width="30" height="20" fill="#4e79a7""##));
+    assert!(svg.contains(r#"<rect x="20" y="10" width="20" height="10" fill="red""#));
+    assert!(svg.contains(">&lt;overlap&gt;</text>"));
+    assert!(!svg.contains("<overlap>"));
+}
+
+#[test]
+fn packings_are_drawn_in_their_bin()
+{
+    let rects = [Rectangle::new(30, 20), Rectangle::new(10, 40), Rectangle::new(20, 20)];
+    let packing = pack(&rects, &PackConfig { bin_width: 64, bin_height: 64, ..PackConfig::default() });
+    let scene = Scene::from_packing(&packing, 0);
+
+    assert_eq!(scene.view(), Some(Rectangle::new(64, 64)));
+    assert_eq!(scene.shapes.len(), 3);
+    assert!(scene.to_ascii(64, 32).contains("1 column = 1, 1 row = 2"));
+    assert!(scene.shapes.iter().any(|s| s.label == "#1"));
+}
+
+#[test]
+fn scenes_wider_than_a_rectangle_are_still_drawn()
+{
+    let mut scene = Scene::new();
+    scene.add(Rectangle::at(i32::MIN, 0, 10, 10), "far left");
+    scene.add(Rectangle::at(i32::MAX, 0, u32::MAX, 10), "far right");
+
+    assert_eq!(scene.view(), Some(Rectangle::at(i32::MIN, 0, u32::MAX, 10)));
+    let drawing = scene.to_ascii(40, 10);
+    let rows: Vec<&str> = drawing.lines().take_while(|line| !line.starts_with("1 column")).collect();
+    assert!(rows.iter().any(|row| row.starts_with('A')) && rows.iter().any(|row| row.ends_with('B')), "{drawing}");
+    assert!(drawing.contains("far right"));
+    assert!(scene.to_svg().contains("far right"));
+}
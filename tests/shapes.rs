@@ -0,0 +1,109 @@
+// The shapes file format and Rectangle's "30x50@10,20" notation
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use rust_progr_lang_course::geometry::{load_shapes, parse_shapes, NamedRect};
+use rust_progr_lang_course::Rectangle;
+
+// A fresh directory for the files of one test
+fn scratch(case: &str) -> PathBuf
+{
+    let dir = env::temp_dir().join(format!("shapes-{}-{case}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn error(text: &str) -> String
+{
+    parse_shapes(text).unwrap_err().to_string()
+}
+
+#[test]
+fn rects_squares_and_comments()
+{
+    let text = "\
+# a house
+rect door 10 20 30 50   # the front one
+square   window -5 10 20
+
+rect \"big hall\" 0 0 100 60
+";
+
+    assert_eq!(
+        parse_shapes(text).unwrap(),
+        [
+            NamedRect { name: String::from("door"), rect: Rectangle::at(10, 20, 30, 50) },
+            NamedRect { name: String::from("window"), rect: Rectangle::at(-5, 10, 20, 20) },
+            NamedRect { name: String::from("big hall"), rect: Rectangle::at(0, 0, 100, 60) },
+        ]
+    );
+}
+
+#[test]
+fn errors_point_at_the_column()
+{
+    assert_eq!(error("rect door 10 20 -30 50"), "<input>:1:17: expected a whole number for width, found '-30'\n    rect door 10 20 -30 50\n                    ^");
+    assert_eq!(error("\n  circle c 1 2 3"), "<input>:2:3: unknown command 'circle', expected rect, square or include\n      circle c 1 2 3\n      ^");
+    assert_eq!(error("square s 1 2"), "<input>:1:13: missing size, usage: square name x y size\n    square s 1 2\n                ^");
+    assert_eq!(error("square s 1 2 3 4"), "<input>:1:16: unexpected '4', usage: square name x y size\n    square s 1 2 3 4\n                   ^");
+    assert!(error("rect \"door 1 2 3 4").starts_with("<input>:1:6: unterminated quote"));
+    assert!(error("square a 0 0 1\nsquare a 0 0 2").starts_with("<input>:2:8: 'a' is already defined at line 1"));
+}
+
+#[test]
+fn includes_are_relative_to_the_including_file()
+{
+    let dir = scratch("include");
+    fs::create_dir_all(dir.join("parts")).unwrap();
+    fs::write(dir.join("main.shapes"), "square first 0 0 1\ninclude \"parts/more.shapes\"\nsquare last 9 9 1\n").unwrap();
+    fs::write(dir.join("parts/more.shapes"), "include deeper.shapes\n").unwrap();
+    fs::write(dir.join("parts/deeper.shapes"), "rect middle 1 2 3 4\n").unwrap();
+
+    let names: Vec<String> = load_shapes(&dir.join("main.shapes")).unwrap().into_iter().map(|s| s.name).collect();
+
+    assert_eq!(names, ["first", "middle", "last"]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn include_errors()
+{
+    let dir = scratch("include-errors");
+    fs::write(dir.join("a.shapes"), "include b.shapes\n").unwrap();
+    fs::write(dir.join("b.shapes"), "square b 0 0 1\ninclude a.shapes\n").unwrap();
+    fs::write(dir.join("c.shapes"), "square b 5 5 5\ninclude b.shapes\n").unwrap();
+    fs::write(dir.join("d.shapes"), "include missing.shapes\n").unwrap();
+
+    let cycle = load_shapes(&dir.join("a.shapes")).unwrap_err();
+    assert_eq!((cycle.line, cycle.column), (2, 9));
+    assert!(cycle.origin.ends_with("b.shapes") && cycle.message.ends_with("a.shapes includes itself"), "{cycle}");
+
+    let duplicate = load_shapes(&dir.join("c.shapes")).unwrap_err();
+    assert!(duplicate.origin.ends_with("b.shapes"), "{duplicate}");
+    assert!(duplicate.message.starts_with("'b' is already defined at ") && duplicate.message.ends_with("c.shapes:1"), "{duplicate}");
+
+    let missing = load_shapes(&dir.join("d.shapes")).unwrap_err();
+    assert!(missing.message.starts_with("can't include ") && missing.column == 9, "{missing}");
+
+    let unreadable = load_shapes(&dir.join("nope.shapes")).unwrap_err().to_string();
+    assert!(unreadable.ends_with("nope.shapes: can't read: No such file or directory (os error 2)"), "{unreadable}");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn rectangle_from_str_and_back()
+{
+    assert_eq!("30x50".parse(), Ok(Rectangle::new(30, 50)));
+    assert_eq!(" 30x50@10,-20 ".parse(), Ok(Rectangle::at(10, -20, 30, 50)));
+    assert_eq!(Rectangle::at(10, -20, 30, 50).to_string(), "30x50@10,-20");
+    assert_eq!(Rectangle::new(30, 50).to_string(), "30x50");
+
+    for bad in ["", "30", "30x", "x50", "-30x50", "30x50@", "30x50@10", "30x50@10,", "30 x 50", "30x50@1.5,2"]
+    {
+        assert!(bad.parse::<Rectangle>().is_err(), "'{bad}' parsed");
+    }
+    assert_eq!("3x".parse::<Rectangle>(), Err(String::from("invalid rectangle '3x', expected WIDTHxHEIGHT or WIDTHxHEIGHT@X,Y")));
+}
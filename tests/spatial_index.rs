@@ -0,0 +1,95 @@
+// The spatial index must give the same answers as looking at every
+// rectangle, after any mix of inserts and removes
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{ItemId, SpatialIndex};
+use rust_progr_lang_course::Rectangle;
+
+fn rectangle(rng: &mut StdRng, spread: i32) -> Rectangle
+{
+    let size = if rng.gen_ratio(1, 20) { 400 } else { 20 };
+    Rectangle::at(rng.gen_range(-spread..spread), rng.gen_range(-spread..spread), rng.gen_range(0..size), rng.gen_range(0..size))
+}
+
+// What a linear scan says, the index has to agree
+fn scan(live: &[(ItemId, Rectangle)], keep: impl Fn(&Rectangle) -> bool) -> Vec<ItemId>
+{
+    let mut ids: Vec<ItemId> = live.iter().filter(|(_, r)| keep(r)).map(|(id, _)| *id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn distance_squared(r: &Rectangle, x: i32, y: i32) -> u128
+{
+    let (x, y) = (i64::from(x), i64::from(y));
+    let dx = (r.left() - x).max(x - r.right()).max(0).unsigned_abs();
+    let dy = (r.top() - y).max(y - r.bottom()).max(0).unsigned_abs();
+    u128::from(dx) * u128::from(dx) + u128::from(dy) * u128::from(dy)
+}
+
+#[test]
+fn matches_a_linear_scan()
+{
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut index = SpatialIndex::new();
+    let mut live: Vec<(ItemId, Rectangle)> = Vec::new();
+
+    for round in 0..3000
+    {
+        if !live.is_empty() && rng.gen_ratio(1, 3)
+        {
+            let (id, rect) = live.swap_remove(rng.gen_range(0..live.len()));
+            assert_eq!(index.remove(id), Some(rect));
+            assert_eq!(index.remove(id), None);
+        }
+        else
+        {
+            let rect = rectangle(&mut rng, 1000);
+            live.push((index.insert(rect), rect));
+        }
+        assert_eq!(index.len(), live.len());
+
+        if round % 10 != 0
+        {
+            continue;
+        }
+
+        let region = rectangle(&mut rng, 1000);
+        assert_eq!(index.query(&region), scan(&live, |r| r.intersects(&region)), "round {round}, {region:?}");
+
+        let (x, y) = (rng.gen_range(-1000..1000), rng.gen_range(-1000..1000));
+        assert_eq!(index.at_point(x, y), scan(&live, |r| r.contains_point(x, y)), "round {round}, ({x}, {y})");
+
+        let closest = live.iter().map(|(id, r)| (distance_squared(r, x, y), *id)).min().map(|(_, id)| id);
+        assert_eq!(index.nearest(x, y), closest, "round {round}, ({x}, {y})");
+    }
+}
+
+#[test]
+fn far_apart_rectangles()
+{
+    let mut index = SpatialIndex::new();
+    let low = index.insert(Rectangle::at(i32::MIN, i32::MIN, 10, 10));
+    let high = index.insert(Rectangle::at(i32::MAX - 5, i32::MAX - 5, u32::MAX, u32::MAX));
+    let everywhere = index.insert(Rectangle::at(i32::MIN, i32::MIN, u32::MAX, u32::MAX));
+
+    assert_eq!(index.at_point(i32::MIN, i32::MIN), [low, everywhere]);
+    assert_eq!(index.at_point(i32::MAX, i32::MAX), [high]);
+    assert_eq!(index.nearest(i32::MAX, i32::MIN), Some(everywhere));
+    assert_eq!(index.query(&Rectangle::at(i32::MAX - 1, 0, 1, 1)), [everywhere]);
+
+    assert_eq!(index.remove(everywhere), Some(Rectangle::at(i32::MIN, i32::MIN, u32::MAX, u32::MAX)));
+    assert_eq!(index.nearest(0, 0), Some(low));
+    assert_eq!(index.iter().map(|(id, _)| id).collect::<Vec<_>>(), [low, high]);
+}
+
+#[test]
+fn empty_index()
+{
+    let index = SpatialIndex::new();
+
+    assert!(index.is_empty());
+    assert_eq!(index.nearest(0, 0), None);
+    assert!(index.query(&Rectangle::new(100, 100)).is_empty());
+}
@@ -0,0 +1,122 @@
+// Affine transforms on points, rectangles and polygons
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_progr_lang_course::geometry::{Point, Polygon, Rect, Rectangle, Transform};
+
+fn close(p: Point<f64>, q: Point<f64>) -> bool
+{
+    p.distance(q) < 1e-9
+}
+
+fn random_transform(rng: &mut StdRng) -> Transform
+{
+    match rng.gen_range(0..4)
+    {
+        0 => Transform::translate(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0)),
+        1 => Transform::scale(rng.gen_range(0.1..4.0), -rng.gen_range(0.1..4.0)),
+        2 => Transform::rotate(rng.gen_range(-PI..PI)),
+        _ => Transform::shear(rng.gen_range(-2.0..2.0), rng.gen_range(-0.4..0.4)),
+    }
+}
+
+#[test]
+fn the_basic_transforms()
+{
+    let p = Point::new(3, 4);
+    assert_eq!(Transform::IDENTITY.apply_point(p), Point::new(3.0, 4.0));
+    assert_eq!(Transform::translate(1.0, -2.0).apply_point(p), Point::new(4.0, 2.0));
+    assert_eq!(Transform::scale(2.0, -1.0).apply_point(p), Point::new(6.0, -4.0));
+    assert_eq!(Transform::shear(1.0, 0.0).apply_point(p), Point::new(7.0, 4.0));
+    assert!(close(Transform::rotate(FRAC_PI_2).apply_point(p), Point::new(-4.0, 3.0)));
+    assert!(close(Transform::rotate_around(PI, Point::new(1.0, 1.0)).apply_point(p), Point::new(-1.0, -2.0)));
+
+    // the order matters
+    let (move_right, turn, origin) = (Transform::translate(10.0, 0.0), Transform::rotate(FRAC_PI_2), Point::new(0.0, 0.0));
+    assert!(close(move_right.then(&turn).apply_point(origin), Point::new(0.0, 10.0)));
+    assert!(close(turn.then(&move_right).apply_point(origin), Point::new(10.0, 0.0)));
+
+    assert_eq!(Transform::scale(0.0, 1.0).inverse(), None);
+    assert_eq!(Transform::scale(2.0, 3.0).determinant(), 6.0);
+    assert_eq!(Transform::default(), Transform::IDENTITY);
+}
+
+#[test]
+fn composing_and_inverting()
+{
+    let mut rng = StdRng::seed_from_u64(50);
+
+    for _ in 0..500
+    {
+        let steps: Vec<Transform> = (0..rng.gen_range(1..6)).map(|_| random_transform(&mut rng)).collect();
+        let composed = steps.iter().fold(Transform::IDENTITY, |all, step| all.then(step));
+        let p = Point::new(rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0));
+
+        let one_by_one = steps.iter().fold(p, |p, step| step.apply_point(p));
+        assert!(close(composed.apply_point(p), one_by_one));
+
+        let inverse = composed.inverse().unwrap();
+        assert!(close(inverse.apply_point(composed.apply_point(p)), p));
+        assert!(close(composed.then(&inverse).apply_point(p), p));
+    }
+}
+
+#[test]
+fn rectangles_get_their_bounding_box()
+{
+    let r = Rectangle::at(10, 20, 30, 10);
+
+    // a quarter turn around the origin, no cell gained from rounding
+    assert_eq!(Transform::rotate(FRAC_PI_2).apply_rectangle(&r), Some(Rectangle::at(-30, 10, 10, 30)));
+    assert_eq!(Transform::translate(-10.0, 5.0).apply_rectangle(&r), Some(Rectangle::at(0, 25, 30, 10)));
+    assert_eq!(Transform::scale(-1.0, 2.0).apply_rectangle(&r), Some(Rectangle::at(-40, 40, 30, 20)));
+    // half a cell covers the whole cell
+    assert_eq!(Transform::translate(0.5, 0.0).apply_rectangle(&r), Some(Rectangle::at(10, 20, 31, 10)));
+    assert_eq!(Transform::translate(1e12, 0.0).apply_rectangle(&r), None);
+
+    let square = Rect::from_origin_size(Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+    let turned = Transform::rotate_around(PI / 4.0, square.center()).apply_rect(&square);
+    let half_diagonal = 2f64.sqrt();
+    assert!(close(turned.origin(), Point::new(1.0 - half_diagonal, 1.0 - half_diagonal)));
+    assert!(close(turned.size(), Point::new(2.0 * half_diagonal, 2.0 * half_diagonal)));
+
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..500
+    {
+        let t = random_transform(&mut rng).then(&random_transform(&mut rng));
+        let r = Rectangle::at(rng.gen_range(-50..50), rng.gen_range(-50..50), rng.gen_range(1..40), rng.gen_range(1..40));
+        let Some(bounds) = t.apply_rectangle(&r) else { continue };
+
+        // every corner is in it, with less than a cell to spare on each side
+        let exact = t.apply_rect(&r);
+        let inside = |p: Point<f64>| {
+            f64::from(bounds.x) <= p.x + 1e-9 && p.x <= bounds.right() as f64 + 1e-9 && f64::from(bounds.y) <= p.y + 1e-9 && p.y <= bounds.bottom() as f64 + 1e-9
+        };
+        assert!(inside(exact.origin()) && inside(exact.end()));
+        assert!(f64::from(bounds.width) < exact.width + 2.0 && f64::from(bounds.height) < exact.height + 2.0);
+    }
+}
+
+#[test]
+fn polygons_keep_their_shape()
+{
+    let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)]);
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for _ in 0..200
+    {
+        let t = random_transform(&mut rng).then(&random_transform(&mut rng));
+        let moved = t.apply_polygon(&triangle);
+
+        // areas grow by the determinant, which is negative for mirrors
+        assert!((moved.signed_area() - triangle.signed_area() * t.determinant()).abs() < 1e-6);
+        let centroid = triangle.centroid().unwrap();
+        assert!(close(moved.centroid().unwrap(), t.apply_point(centroid)));
+    }
+
+    // turning doesn't change lengths
+    let turned = Transform::rotate(1.0).apply_polygon(&triangle);
+    assert!((turned.perimeter() - 12.0).abs() < 1e-9);
+}